@@ -5,7 +5,7 @@ use std::io::{self, Seek};
 use std::time::Instant;
 
 use mzdata::io::{
-    infer_from_stream, MassSpectrometryFormat, PreBufferedStream, RestartableGzDecoder,
+    infer_from_stream, MassSpectrometryFormat, PreBufferedStream, RestartableDecoder,
     SpectrumSource,
 };
 use mzdata::{MGFReader, MzMLReader};
@@ -19,7 +19,7 @@ fn main() -> io::Result<()> {
     let groups: Vec<_> = match fmt {
         MassSpectrometryFormat::MGF => {
             if compressed {
-                MGFReader::new(RestartableGzDecoder::new(io::BufReader::new(stream)))
+                MGFReader::new(RestartableDecoder::detect(io::BufReader::new(stream))?)
                     .into_groups()
                     .collect()
             } else {
@@ -28,7 +28,7 @@ fn main() -> io::Result<()> {
         }
         MassSpectrometryFormat::MzML => {
             if compressed {
-                MzMLReader::new(RestartableGzDecoder::new(io::BufReader::new(stream)))
+                MzMLReader::new(RestartableDecoder::detect(io::BufReader::new(stream))?)
                     .into_groups()
                     .collect()
             } else {
@@ -1,4 +1,4 @@
-use std::{io, path};
+use std::{fmt::Display, io, path};
 
 use flate2::bufread::MultiGzDecoder;
 use std::io::prelude::*;
@@ -7,47 +7,225 @@ pub fn is_gzipped(header: &[u8]) -> bool {
     header.starts_with(b"\x1f\x8b")
 }
 
-pub fn is_gzipped_extension(path: path::PathBuf) -> (bool, path::PathBuf) {
-    if let Some(ext) = path.extension() {
-        if ext.to_ascii_lowercase() == "gz" {
-            (true, path.with_extension(""))
+pub fn is_bzipped(header: &[u8]) -> bool {
+    header.starts_with(b"BZh")
+}
+
+pub fn is_xz(header: &[u8]) -> bool {
+    header.starts_with(b"\xfd7zXZ\x00")
+}
+
+/// The compression applied to an entire file or stream, as opposed to the compression
+/// of individual binary data arrays within it.
+///
+/// Gzip decompression is always available, while bzip2 and xz decompression require the
+/// `bzip2` and `xz` features respectively.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileCompression {
+    #[default]
+    None,
+    Gzip,
+    Bzip2,
+    Xz,
+}
+
+impl Display for FileCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::None => "uncompressed",
+            Self::Gzip => "gzip",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FileCompression {
+    /// Detect the compression from the magic bytes at the start of a stream
+    pub fn from_header(header: &[u8]) -> Self {
+        if is_gzipped(header) {
+            Self::Gzip
+        } else if is_bzipped(header) {
+            Self::Bzip2
+        } else if is_xz(header) {
+            Self::Xz
+        } else {
+            Self::None
+        }
+    }
+
+    /// Detect the compression from the extension of `path`, returning the path with
+    /// that extension removed if it was recognized
+    pub fn from_extension(path: path::PathBuf) -> (Self, path::PathBuf) {
+        let compression = match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("gz") => Self::Gzip,
+            Some("bz2") => Self::Bzip2,
+            Some("xz") => Self::Xz,
+            _ => Self::None,
+        };
+        if compression.is_compressed() {
+            (compression, path.with_extension(""))
         } else {
-            (false, path)
+            (compression, path)
+        }
+    }
+
+    pub const fn is_compressed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Whether this build of the library can decompress this kind of compression
+    pub const fn is_supported(&self) -> bool {
+        match self {
+            Self::None | Self::Gzip => true,
+            Self::Bzip2 => cfg!(feature = "bzip2"),
+            Self::Xz => cfg!(feature = "xz"),
+        }
+    }
+
+    fn unsupported(&self) -> io::Error {
+        match self {
+            Self::None => io::Error::new(io::ErrorKind::InvalidInput, "The stream is not compressed"),
+            _ => io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Reading {self} compressed streams requires the `{self}` feature"),
+            ),
         }
-    } else {
-        (false, path)
     }
 }
 
-/// A [`flate2::bufread::MultiGzDecoder`] that implements [`std::io::Seek`] by
-/// by incrementally reading ahead, or rewinding to the beginning of the file and
-/// doing the same.
+/// Decompress up to `size` bytes from the start of a compressed stream, given the
+/// `prefix` already read from the front of `stream`.
+///
+/// The prefix is replayed into the decompressor ahead of the rest of `stream`, which is
+/// read only as far as needed. Block-based formats like bzip2 cannot produce any output
+/// until an entire block has been read, so the prefix alone may not be enough.
+pub(crate) fn decompress_prefix<R: Read>(
+    compression: FileCompression,
+    prefix: Vec<u8>,
+    stream: R,
+    size: usize,
+) -> io::Result<Vec<u8>> {
+    let handle = io::BufReader::new(io::Cursor::new(prefix).chain(stream));
+    let decoder = Decoder::new(handle, compression)?;
+    let mut buf = Vec::with_capacity(size);
+    decoder.take(size as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+enum Decoder<R: BufRead> {
+    Gzip(MultiGzDecoder<R>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::bufread::MultiBzDecoder<R>),
+    #[cfg(feature = "xz")]
+    Xz(xz2::bufread::XzDecoder<R>),
+}
+
+impl<R: BufRead> Decoder<R> {
+    fn new(handle: R, compression: FileCompression) -> io::Result<Self> {
+        match compression {
+            FileCompression::Gzip => Ok(Self::Gzip(MultiGzDecoder::new(handle))),
+            #[cfg(feature = "bzip2")]
+            FileCompression::Bzip2 => Ok(Self::Bzip2(bzip2::bufread::MultiBzDecoder::new(
+                handle,
+            ))),
+            #[cfg(feature = "xz")]
+            FileCompression::Xz => Ok(Self::Xz(xz2::bufread::XzDecoder::new_multi_decoder(
+                handle,
+            ))),
+            _ => Err(compression.unsupported()),
+        }
+    }
+
+    fn compression(&self) -> FileCompression {
+        match self {
+            Self::Gzip(_) => FileCompression::Gzip,
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(_) => FileCompression::Bzip2,
+            #[cfg(feature = "xz")]
+            Self::Xz(_) => FileCompression::Xz,
+        }
+    }
+
+    fn into_inner(self) -> R {
+        match self {
+            Self::Gzip(d) => d.into_inner(),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(d) => d.into_inner(),
+            #[cfg(feature = "xz")]
+            Self::Xz(d) => d.into_inner(),
+        }
+    }
+}
+
+impl<R: BufRead> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Gzip(d) => d.read(buf),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(d) => d.read(buf),
+            #[cfg(feature = "xz")]
+            Self::Xz(d) => d.read(buf),
+        }
+    }
+}
+
+/// A decompressing reader over a gzip, bzip2 or xz compressed stream that implements
+/// [`std::io::Seek`] by incrementally reading ahead, or rewinding to the beginning of
+/// the file and doing the same.
 ///
 /// Not intended to be efficient, but provides a workable interface.
-pub struct RestartableGzDecoder<R: BufRead + Seek> {
-    handle: Option<MultiGzDecoder<R>>,
+pub struct RestartableDecoder<R: BufRead + Seek> {
+    handle: Option<Decoder<R>>,
     offset: u64,
 }
 
-impl<R: BufRead + Seek> RestartableGzDecoder<R> {
-    pub fn new(handle: R) -> Self {
-        Self {
-            handle: Some(MultiGzDecoder::new(handle)),
+impl<R: BufRead + Seek> RestartableDecoder<R> {
+    /// Wrap `handle` in a decoder for `compression`.
+    ///
+    /// # Errors
+    /// This returns an error if `compression` is [`FileCompression::None`] or if the
+    /// feature needed to decompress it is not enabled.
+    pub fn new(handle: R, compression: FileCompression) -> io::Result<Self> {
+        Ok(Self {
+            handle: Some(Decoder::new(handle, compression)?),
             offset: 0,
-        }
+        })
+    }
+
+    /// Wrap `handle` in a decoder for the compression detected from the bytes at
+    /// the start of its buffer.
+    ///
+    /// # Errors
+    /// See [`RestartableDecoder::new`].
+    pub fn detect(mut handle: R) -> io::Result<Self> {
+        let compression = FileCompression::from_header(handle.fill_buf()?);
+        Self::new(handle, compression)
+    }
+
+    /// The kind of compression being decoded
+    pub fn compression(&self) -> FileCompression {
+        self.handle.as_ref().unwrap().compression()
     }
 
     fn reset(&mut self) -> io::Result<u64> {
         let handle = self.handle.take().unwrap();
+        let compression = handle.compression();
         let mut inner = handle.into_inner();
         let res = inner.seek(io::SeekFrom::Start(0));
-        self.handle = Some(MultiGzDecoder::new(inner));
+        self.handle = Some(Decoder::new(inner, compression)?);
         self.offset = 0;
         res
     }
 }
 
-impl<R: BufRead + Seek> Read for RestartableGzDecoder<R> {
+impl<R: BufRead + Seek> Read for RestartableDecoder<R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         let handle = self.handle.as_mut().unwrap();
         match handle.read(buf) {
@@ -60,7 +238,7 @@ impl<R: BufRead + Seek> Read for RestartableGzDecoder<R> {
     }
 }
 
-impl<R: BufRead + Seek> Seek for RestartableGzDecoder<R> {
+impl<R: BufRead + Seek> Seek for RestartableDecoder<R> {
     fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
         match pos {
             io::SeekFrom::Start(o) => {
@@ -71,7 +249,7 @@ impl<R: BufRead + Seek> Seek for RestartableGzDecoder<R> {
             }
             io::SeekFrom::End(_) => Err(io::Error::new(
                 io::ErrorKind::Unsupported,
-                "Cannot seek relative to end of a gzip stream",
+                "Cannot seek relative to end of a compressed stream",
             )),
             io::SeekFrom::Current(o) => match o {
                 0 => Ok(self.offset),
@@ -95,6 +273,35 @@ impl<R: BufRead + Seek> Seek for RestartableGzDecoder<R> {
     }
 }
 
+/// A [`flate2::bufread::MultiGzDecoder`] that implements [`std::io::Seek`] by
+/// by incrementally reading ahead, or rewinding to the beginning of the file and
+/// doing the same.
+///
+/// Not intended to be efficient, but provides a workable interface. See
+/// [`RestartableDecoder`] for other kinds of compression.
+pub struct RestartableGzDecoder<R: BufRead + Seek>(RestartableDecoder<R>);
+
+impl<R: BufRead + Seek> RestartableGzDecoder<R> {
+    pub fn new(handle: R) -> Self {
+        Self(RestartableDecoder {
+            handle: Some(Decoder::Gzip(MultiGzDecoder::new(handle))),
+            offset: 0,
+        })
+    }
+}
+
+impl<R: BufRead + Seek> Read for RestartableGzDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: BufRead + Seek> Seek for RestartableGzDecoder<R> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
@@ -115,4 +322,64 @@ mod test {
         assert!(buf.contains("controllerType=0 controllerNumber=1 scan=1"));
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_detect_compression() -> io::Result<()> {
+        let (compression, path) = FileCompression::from_extension("small.mzML.bz2".into());
+        assert_eq!(compression, FileCompression::Bzip2);
+        assert_eq!(path, path::PathBuf::from("small.mzML"));
+        let (compression, path) = FileCompression::from_extension("small.mzML".into());
+        assert_eq!(compression, FileCompression::None);
+        assert_eq!(path, path::PathBuf::from("small.mzML"));
+
+        let handle = io::BufReader::new(fs::File::open("test/data/small.mzML.gz")?);
+        let reader = RestartableDecoder::detect(handle)?;
+        assert_eq!(reader.compression(), FileCompression::Gzip);
+
+        let handle = io::BufReader::new(fs::File::open("test/data/small.mzML")?);
+        assert!(RestartableDecoder::detect(handle).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn exercise_restartable_bzip2() -> io::Result<()> {
+        let content = fs::read("test/data/small.mgf")?;
+        let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+        encoder.write_all(&content)?;
+        let compressed = encoder.finish()?;
+        assert_eq!(FileCompression::from_header(&compressed), FileCompression::Bzip2);
+
+        let mut reader = RestartableDecoder::detect(io::Cursor::new(compressed))?;
+        reader.seek(io::SeekFrom::Current(100))?;
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, &content[100..]);
+        reader.seek(io::SeekFrom::Start(0))?;
+        buf.clear();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, content);
+        Ok(())
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn exercise_restartable_xz() -> io::Result<()> {
+        let content = fs::read("test/data/small.mgf")?;
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(&content)?;
+        let compressed = encoder.finish()?;
+        assert_eq!(FileCompression::from_header(&compressed), FileCompression::Xz);
+
+        let mut reader = RestartableDecoder::detect(io::Cursor::new(compressed))?;
+        reader.seek(io::SeekFrom::Current(100))?;
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, &content[100..]);
+        reader.seek(io::SeekFrom::Start(0))?;
+        buf.clear();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, content);
+        Ok(())
+    }
+}
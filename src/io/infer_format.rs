@@ -7,7 +7,7 @@ use std::path::{self, Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender, SyncSender};
 use std::any::Any;
 
-use flate2::{bufread::GzDecoder, write::GzEncoder};
+use flate2::write::GzEncoder;
 use mzpeaks::{CentroidLike, CentroidPeak, DeconvolutedCentroidLike, DeconvolutedPeak};
 #[cfg(feature = "bruker_tdf")]
 use mzpeaks::{feature::{ChargedFeature, Feature}, IonMobility, Mass, MZ};
@@ -17,7 +17,7 @@ use crate::params::ControlledVocabulary;
 #[cfg(feature = "mzmlb")]
 pub use crate::io::mzmlb::{MzMLbReaderType, MzMLbWriterBuilder};
 
-use crate::io::compression::{is_gzipped, is_gzipped_extension, RestartableGzDecoder};
+use crate::io::compression::{decompress_prefix, FileCompression, RestartableDecoder};
 use crate::io::mgf::{is_mgf, MGFReaderType, MGFWriterType};
 use crate::io::mzml::{is_mzml, MzMLReaderType, MzMLWriterType};
 use crate::io::traits::{RandomAccessSpectrumIterator, SpectrumSource, SpectrumWriter, MZFileReader};
@@ -284,7 +284,7 @@ impl<R: io::Read + io::Seek,
     pub fn open_read_seek(mut stream: R) -> io::Result<Self> {
         let (fmt, gzipped) = infer_from_stream(&mut stream)?;
         if gzipped {
-            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support gzipped streams"))
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support compressed streams"))
         }
         match fmt {
             MassSpectrometryFormat::MGF => Ok(Self::MGF(MGFReaderType::new_indexed(stream))),
@@ -348,7 +348,7 @@ impl<R: io::Read,
         let (fmt, gzipped) = infer_from_stream(&mut stream)?;
 
         if gzipped {
-            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support gzipped streams"))
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support compressed streams"))
         }
 
         let reader = match fmt {
@@ -370,7 +370,7 @@ impl<R: io::Read,
         let (fmt, gzipped) = infer_from_stream(&mut stream)?;
 
         if gzipped {
-            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support gzipped streams"))
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "This method does not support compressed streams"))
         }
 
         let reader = match fmt {
@@ -412,7 +412,7 @@ impl<C: CentroidLike + Default + From<CentroidPeak> + BuildFromArrayMap,
         if is_gzipped {
             return Err(io::Error::new(
                 io::ErrorKind::Unsupported,
-                "Gzipped files are not supported",
+                "Compressed files are not supported",
             ))
         }
         match format {
@@ -448,7 +448,7 @@ impl<C: CentroidLike + Default + From<CentroidPeak> + BuildFromArrayMap,
         if is_gzipped {
             return Err(io::Error::new(
                 io::ErrorKind::Unsupported,
-                "Gzipped files are not supported",
+                "Compressed files are not supported",
             ))
         }
         match format {
@@ -629,7 +629,7 @@ impl<C: CentroidLike + Default + From<CentroidPeak> + BuildFromArrayMap,
 }
 
 /// Given a path, infer the file format and whether or not the file at that path is
-/// GZIP compressed
+/// compressed with gzip, bzip2 or xz
 pub fn infer_from_path<P: Into<path::PathBuf>>(path: P) -> (MassSpectrometryFormat, bool) {
     let path: path::PathBuf = path.into();
     if path.is_dir() {
@@ -640,7 +640,8 @@ pub fn infer_from_path<P: Into<path::PathBuf>>(path: P) -> (MassSpectrometryForm
             return (MassSpectrometryFormat::Unknown, false)
         }
     }
-    let (is_gzipped, path) = is_gzipped_extension(path);
+    let (compression, path) = FileCompression::from_extension(path);
+    let is_compressed = compression.is_compressed();
     if let Some(ext) = path.extension() {
         if let Some(ext) = ext.to_ascii_lowercase().to_str() {
             let form = match ext {
@@ -652,17 +653,21 @@ pub fn infer_from_path<P: Into<path::PathBuf>>(path: P) -> (MassSpectrometryForm
                 "raw" => MassSpectrometryFormat::ThermoRaw,
                 _ => MassSpectrometryFormat::Unknown,
             };
-            (form, is_gzipped)
+            (form, is_compressed)
         } else {
-            (MassSpectrometryFormat::Unknown, is_gzipped)
+            (MassSpectrometryFormat::Unknown, is_compressed)
         }
     } else {
-        (MassSpectrometryFormat::Unknown, is_gzipped)
+        (MassSpectrometryFormat::Unknown, is_compressed)
     }
 }
 
 /// Given a stream of bytes, infer the file format and whether or not the
-/// stream is GZIP compressed. This assumes the stream is seekable.
+/// stream is compressed with gzip, bzip2 or xz. This assumes the stream is seekable.
+///
+/// # Errors
+/// Besides I/O errors, this returns an error if the stream is compressed in a way that
+/// requires a feature that is not enabled, like `bzip2` or `xz`.
 pub fn infer_from_stream<R: Read + Seek>(
     stream: &mut R,
 ) -> io::Result<(MassSpectrometryFormat, bool)> {
@@ -673,32 +678,28 @@ pub fn infer_from_stream<R: Read + Seek>(
     let current_pos = stream.stream_position()?;
     // record how many bytes were actually read so we know the upper bound
     let bytes_read = stream.read(buf.as_mut_slice())?;
-    buf.shrink_to(bytes_read);
-    let is_stream_gzipped = is_gzipped(buf.as_slice());
-    if is_stream_gzipped {
-        let mut decompressed_buf = Vec::new();
-        // In the worst case, we can't have fewer bytes than those that were read in (minus the size of the gzip header)
-        // and we assume the compression ratio means we have recouped that. We read in only that many bytes
-        // decompressed because the decompressor treats an incomplete segment as an error and thus using
-        // io::Read::read_to_end is not an option.
-        decompressed_buf.resize(bytes_read, b'\0');
-        let mut decoder = GzDecoder::new(io::Cursor::new(buf));
-        decoder.read_exact(&mut decompressed_buf)?;
-        buf = decompressed_buf;
+    buf.truncate(bytes_read);
+    let compression = FileCompression::from_header(buf.as_slice());
+    let is_stream_compressed = compression.is_compressed();
+    if is_stream_compressed {
+        // Decompress as many bytes as were read in. The prefix is replayed into the decompressor
+        // ahead of the rest of the stream, because the compressed prefix may not hold that many
+        // bytes, and an incomplete segment or block is treated as an error.
+        buf = decompress_prefix(compression, buf, stream.by_ref(), bytes_read)?;
     }
     stream.seek(io::SeekFrom::Start(current_pos))?;
 
     match &buf {
-        _ if is_mzml(&buf) => Ok((MassSpectrometryFormat::MzML, is_stream_gzipped)),
-        _ if is_mgf(&buf) => Ok((MassSpectrometryFormat::MGF, is_stream_gzipped)),
+        _ if is_mzml(&buf) => Ok((MassSpectrometryFormat::MzML, is_stream_compressed)),
+        _ if is_mgf(&buf) => Ok((MassSpectrometryFormat::MGF, is_stream_compressed)),
         #[cfg(feature = "thermo")]
-        _ if is_thermo_raw_prefix(&buf) => Ok((MassSpectrometryFormat::ThermoRaw, is_stream_gzipped)),
-        _ => Ok((MassSpectrometryFormat::Unknown, is_stream_gzipped))
+        _ if is_thermo_raw_prefix(&buf) => Ok((MassSpectrometryFormat::ThermoRaw, is_stream_compressed)),
+        _ => Ok((MassSpectrometryFormat::Unknown, is_stream_compressed))
     }
 }
 
 /// Given a path, infer the file format and whether or not the file at that path is
-/// GZIP compressed, using both the file name and by trying to open and read the file
+/// compressed with gzip, bzip2 or xz, using both the file name and by trying to open and read the file
 /// header
 pub fn infer_format<P: Into<path::PathBuf>>(path: P) -> io::Result<(MassSpectrometryFormat, bool)> {
     let path: path::PathBuf = path.into();
@@ -944,7 +945,7 @@ pub trait MassSpectrometryReadWriteProcess<
                     MassSpectrometryFormat::MGF => {
                         let handle = fs::File::open(read_path)?;
                         if is_gzipped {
-                            let fh = RestartableGzDecoder::new(io::BufReader::new(handle));
+                            let fh = RestartableDecoder::detect(io::BufReader::new(handle))?;
                             let reader = StreamingSpectrumIterator::new(MGFReaderType::new(fh));
                             let reader = self.transform_reader(reader, format)?;
                             self.open_writer(reader, format, write_path)?;
@@ -959,7 +960,7 @@ pub trait MassSpectrometryReadWriteProcess<
                         let handle = fs::File::open(read_path)?;
 
                         if is_gzipped {
-                            let fh = RestartableGzDecoder::new(io::BufReader::new(handle));
+                            let fh = RestartableDecoder::detect(io::BufReader::new(handle))?;
                             let reader = StreamingSpectrumIterator::new(MzMLReaderType::new(fh));
                             let reader = self.transform_reader(reader, format)?;
                             self.open_writer(reader, format, write_path)?;
@@ -1049,7 +1050,7 @@ pub trait MassSpectrometryReadWriteProcess<
                     MassSpectrometryFormat::MGF => {
                         if compressed {
                             let reader = StreamingSpectrumIterator::new(MGFReaderType::new(
-                                RestartableGzDecoder::new(io::BufReader::new(buffered)),
+                                RestartableDecoder::detect(io::BufReader::new(buffered))?,
                             ));
                             let reader = self.transform_reader(reader, ms_format)?;
                             self.open_writer(reader, ms_format, write_path)?;
@@ -1063,7 +1064,7 @@ pub trait MassSpectrometryReadWriteProcess<
                     MassSpectrometryFormat::MzML => {
                         if compressed {
                             let reader = StreamingSpectrumIterator::new(MzMLReaderType::new(
-                                RestartableGzDecoder::new(io::BufReader::new(buffered)),
+                                RestartableDecoder::detect(io::BufReader::new(buffered))?,
                             ));
                             let reader = self.transform_reader(reader, ms_format)?;
                             self.open_writer(reader, ms_format, write_path)?;
@@ -1405,4 +1406,51 @@ mod test {
         assert!(!gzip);
         Ok(())
     }
+
+    #[cfg(not(feature = "bzip2"))]
+    #[test]
+    fn test_infer_stream_unsupported_compression() {
+        let mut stream = io::Cursor::new(b"BZh91AY&SY".to_vec());
+        let err = infer_from_stream(&mut stream).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn test_infer_stream_bzip2() -> io::Result<()> {
+        let tmpdir = tempfile::tempdir()?;
+        let path = tmpdir.path().join("small.mzML.bz2");
+        let mut encoder =
+            bzip2::write::BzEncoder::new(fs::File::create(&path)?, bzip2::Compression::fast());
+        io::copy(&mut fs::File::open("./test/data/small.mzML")?, &mut encoder)?;
+        encoder.finish()?;
+
+        assert_eq!(infer_from_path(&path), (MassSpectrometryFormat::MzML, true));
+        let (form, compressed) = infer_from_stream(&mut fs::File::open(&path)?)?;
+        assert_eq!(form, MassSpectrometryFormat::MzML);
+        assert!(compressed);
+
+        let n = crate::mz_read!(path.as_path(), reader => { reader.count() })?;
+        assert_eq!(n, 48);
+        Ok(())
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn test_infer_stream_xz() -> io::Result<()> {
+        let tmpdir = tempfile::tempdir()?;
+        let path = tmpdir.path().join("small.mgf.xz");
+        let mut encoder = xz2::write::XzEncoder::new(fs::File::create(&path)?, 6);
+        io::copy(&mut fs::File::open("./test/data/small.mgf")?, &mut encoder)?;
+        encoder.finish()?;
+
+        assert_eq!(infer_from_path(&path), (MassSpectrometryFormat::MGF, true));
+        let (form, compressed) = infer_from_stream(&mut fs::File::open(&path)?)?;
+        assert_eq!(form, MassSpectrometryFormat::MGF);
+        assert!(compressed);
+
+        let n = crate::mz_read!(path.as_path(), reader => { reader.count() })?;
+        assert_eq!(n, 35);
+        Ok(())
+    }
 }
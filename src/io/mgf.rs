@@ -172,6 +172,92 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_read_scans_range() -> io::Result<()> {
+        let text = "BEGIN IONS
+TITLE=single
+SCANS=42
+PEPMASS=500.25
+CHARGE=2+
+100.0 10.0
+END IONS
+BEGIN IONS
+TITLE=merged
+SCANS=100-105
+PEPMASS=600.5
+CHARGE=2+
+100.0 10.0
+200.0 20.0
+END IONS
+BEGIN IONS
+TITLE=earlier
+SCANS=7
+PEPMASS=700.5
+CHARGE=2+
+100.0 10.0
+END IONS
+";
+        let mut reader = MGFReader::new_indexed(io::Cursor::new(text.as_bytes()));
+        assert_eq!(reader.len(), 3);
+
+        let scan = reader.get_spectrum_by_index(1).unwrap();
+        assert_eq!(scan.id(), "merged");
+        assert_eq!(scan.get_param_by_name("first scan").unwrap().to_i32().unwrap(), 100);
+        assert_eq!(scan.get_param_by_name("last scan").unwrap().to_i32().unwrap(), 105);
+        assert_eq!(scan.get_param_by_name("scans").unwrap().value.to_string(), "100-105");
+
+        let scan = reader.get_spectrum_by_index(0).unwrap();
+        assert!(scan.get_param_by_name("first scan").is_none());
+
+        assert_eq!(reader.index_of_scan_number(42), Some(0));
+        for i in 100..=105 {
+            assert_eq!(reader.get_spectrum_by_scan_number(i).unwrap().id(), "merged");
+        }
+        assert!(reader.get_spectrum_by_scan_number(99).is_none());
+        assert!(reader.get_spectrum_by_scan_number(106).is_none());
+        assert_eq!(reader.index_of_scan_number(7), Some(2));
+        assert!(reader.index_of_scan_number(8).is_none());
+        assert!(reader.index_of_scan_number(1).is_none());
+
+        // The parameters derived from a range are not written back as headers
+        let scan = reader.get_spectrum_by_index(1).unwrap();
+        let mut writer = MGFWriter::new(io::Cursor::new(Vec::new()));
+        writer.write(&scan)?;
+        writer.flush()?;
+        let buffer = writer.handle.into_inner()?.into_inner();
+        let written = String::from_utf8_lossy(&buffer);
+        assert!(!written.contains("FIRST_SCAN="));
+        assert!(!written.contains("LAST_SCAN="));
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_id_policy() -> io::Result<()> {
+        let text = "BEGIN IONS
+TITLE=dup
+PEPMASS=500.25
+100.0 10.0
+END IONS
+BEGIN IONS
+TITLE=dup
+PEPMASS=600.5
+100.0 10.0
+END IONS
+";
+        let mut reader = MGFReader::new_indexed(io::Cursor::new(text.as_bytes()));
+        assert_eq!(reader.len(), 1);
+        let mz = reader.get_spectrum_by_id("dup").unwrap().precursor().unwrap().ion().mz;
+        assert_eq!(mz, 600.5);
+        assert!(reader.validate().is_err());
+
+        // Changing the policy of an indexed reader rebuilds the index
+        reader.set_duplicate_id_policy(crate::io::DuplicateIdPolicy::MakeUnique);
+        assert_eq!(reader.len(), 2);
+        assert_eq!(reader.get_spectrum_by_index(1).unwrap().id(), "dup");
+        assert!(reader.get_spectrum_by_id("dup_1").is_some());
+        Ok(())
+    }
+
     #[cfg(feature = "async")]
     mod async_tests {
         use super::*;
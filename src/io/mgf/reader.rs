@@ -7,13 +7,13 @@ use std::{
     str,
 };
 
-use log::warn;
+use log::{error, warn};
 use thiserror::Error;
 
 use mzpeaks::{CentroidPeak, DeconvolutedPeak};
 
 use super::super::{
-    offset_index::OffsetIndex,
+    offset_index::{DuplicateIdError, DuplicateIdPolicy, OffsetIndex},
     traits::{
         ChromatogramSource, MZFileReader, RandomAccessSpectrumIterator, SeekRead,
         SpectrumAccessError, SpectrumSource,
@@ -195,11 +195,35 @@ pub struct MGFReaderType<
     data_processings: Vec<DataProcessing>,
     run: MassSpectrometryRun,
     read_counter: usize,
+    /// The inclusive `SCANS` range of each spectrum paired with its position in the offset
+    /// index, sorted by the first scan number and populated by [`MGFReaderType::build_index`]
+    scan_number_index: Vec<(u64, u64, usize)>,
     pub detail_level: DetailLevel,
     centroid_type: PhantomData<C>,
     deconvoluted_type: PhantomData<D>,
 }
 
+/// Parse the value of a `SCANS` header, either a single scan number or an
+/// inclusive `first-last` range, into a `(first, last)` pair.
+pub(crate) fn parse_scans_range(value: &str) -> Option<(u64, u64)> {
+    let value = value.trim();
+    match value.split_once('-') {
+        Some((first, last)) => {
+            let first: u64 = first.trim().parse().ok()?;
+            let last: u64 = last.trim().parse().ok()?;
+            if first <= last {
+                Some((first, last))
+            } else {
+                Some((last, first))
+            }
+        }
+        None => {
+            let scan: u64 = value.parse().ok()?;
+            Some((scan, scan))
+        }
+    }
+}
+
 
 pub(crate) trait MGFLineParsing<C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> {
     fn state(&self) -> &MGFParserState;
@@ -270,6 +294,25 @@ pub(crate) trait MGFLineParsing<C: CentroidPeakAdapting, D: DeconvolutedPeakAdap
             builder.empty_metadata = false;
             match key {
                 "TITLE" => builder.description.id = value.to_string(),
+                "SCANS" => {
+                    // Merged spectra may list a range of scans, `first-last`, which
+                    // are recorded as separate parameters alongside the raw value.
+                    match parse_scans_range(value) {
+                        Some((first, last)) if first != last => {
+                            builder
+                                .description
+                                .add_param(Param::new_key_value("first scan", first.to_string()));
+                            builder
+                                .description
+                                .add_param(Param::new_key_value("last scan", last.to_string()));
+                        }
+                        Some(_) => {}
+                        None => warn!("Failed to parse SCANS value {value}"),
+                    }
+                    builder
+                        .description
+                        .add_param(Param::new_key_value("scans", value));
+                }
                 "RTINSECONDS" => {
                     let scan_ev = builder
                         .description
@@ -566,7 +609,8 @@ impl<R: io::Read, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> MGFReade
             file_description: Self::default_file_description(),
             detail_level: DetailLevel::Full,
             run: MassSpectrometryRun::default(),
-            read_counter: 0
+            read_counter: 0,
+            scan_number_index: Vec::new(),
         }
     }
 }
@@ -597,6 +641,9 @@ impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> MGFReade
 
     /// Builds an offset index to each `BEGIN IONS` line
     /// by doing a fast pre-scan of the text file.
+    ///
+    /// Duplicate IDs never make this fail. Under [`DuplicateIdPolicy::Error`] they are left out
+    /// of the index and logged, and [`MGFReaderType::validate`] reports them.
     pub fn build_index(&mut self) -> u64 {
         let mut offset: u64 = 0;
         let mut last_start: u64 = 0;
@@ -612,6 +659,10 @@ impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> MGFReade
 
         let mut buffer: Vec<u8> = Vec::new();
         let mut index = OffsetIndex::new("spectrum".into());
+        index.duplicate_id_policy = self.index.duplicate_id_policy;
+        let mut scan_number_index = Vec::new();
+        let mut scans_range: Option<(u64, u64)> = None;
+        let mut block_position: Option<usize> = None;
         loop {
             buffer.clear();
             let b = match self.handle.read_until(b'\n', &mut buffer) {
@@ -626,10 +677,34 @@ impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> MGFReade
             if buffer.starts_with(b"BEGIN IONS") {
                 found_start = true;
                 last_start = offset;
+                scans_range = None;
+                block_position = None;
+            } else if buffer.starts_with(b"END IONS") {
+                if let (Some((first, last)), Some(position)) =
+                    (scans_range.take(), block_position.take())
+                {
+                    scan_number_index.push((first, last, position));
+                }
+            } else if buffer.starts_with(b"SCANS=") {
+                scans_range = str::from_utf8(&buffer[6..])
+                    .ok()
+                    .and_then(parse_scans_range);
             } else if found_start && buffer.starts_with(b"TITLE=") {
                 match str::from_utf8(&buffer[6..]) {
                     Ok(string) => {
-                        index.insert(string.trim().to_owned(), last_start);
+                        let key = string.trim();
+                        index.insert(key.to_owned(), last_start);
+                        // Find where this block landed, if the duplicate ID policy kept it
+                        block_position = match index.get_index(index.len().saturating_sub(1)) {
+                            Some((_, o)) if o == last_start => Some(index.len() - 1),
+                            _ if index.get(key) == Some(last_start) => {
+                                // An earlier block was replaced in place, drop its scans
+                                let position = index.index_of(key);
+                                scan_number_index.retain(|(_, _, p)| Some(*p) != position);
+                                position
+                            }
+                            _ => None,
+                        };
                     }
                     Err(_err) => {}
                 };
@@ -642,11 +717,66 @@ impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting> MGFReade
             .expect("Failed to restore location");
         index.init = true;
         self.set_index(index);
+        scan_number_index.sort_by_key(|(first, _, _)| *first);
+        self.scan_number_index = scan_number_index;
         if self.index.is_empty() {
             warn!("An index was built but no entries were found")
         }
+        if matches!(self.index.duplicate_id_policy, DuplicateIdPolicy::Error) {
+            if let Err(err) = self.index.validate() {
+                error!("{err}, the duplicates were left out of the index");
+            }
+        }
         offset
     }
+
+    /// How duplicate spectrum IDs (`TITLE`) are handled when building the offset index
+    pub fn duplicate_id_policy(&self) -> DuplicateIdPolicy {
+        self.index.duplicate_id_policy
+    }
+
+    /// Set how duplicate spectrum IDs (`TITLE`) are handled when building the offset index.
+    /// If the reader has already been indexed, e.g. by [`MZFileReader::open_path`], the index
+    /// is rebuilt with the new policy.
+    ///
+    /// A [`DuplicateIdPolicy::Error`] cannot fail here. Instead the duplicates are left out of
+    /// the index, and [`MGFReaderType::validate`] reports them.
+    pub fn set_duplicate_id_policy(&mut self, policy: DuplicateIdPolicy) {
+        let changed = policy != self.index.duplicate_id_policy;
+        self.index.duplicate_id_policy = policy;
+        if changed && self.index.init {
+            self.build_index();
+        }
+    }
+
+    /// Check the offset index for any duplicate IDs encountered while building it.
+    /// See [`OffsetIndex::validate`].
+    pub fn validate(&self) -> Result<(), DuplicateIdError> {
+        self.index.validate()
+    }
+
+    /// Find the index of the spectrum whose `SCANS` entry includes `scan_number`,
+    /// either as a single scan or anywhere within a `first-last` range.
+    ///
+    /// This requires that the index has been built with [`MGFReaderType::build_index`]. The
+    /// lookup is a binary search over the ranges ordered by their first scan number, so if
+    /// ranges overlap, only the one starting closest below `scan_number` is considered.
+    pub fn index_of_scan_number(&self, scan_number: u64) -> Option<usize> {
+        let i = self
+            .scan_number_index
+            .partition_point(|(first, _, _)| *first <= scan_number);
+        let (_, last, position) = self.scan_number_index.get(i.checked_sub(1)?)?;
+        (scan_number <= *last).then_some(*position)
+    }
+
+    /// Retrieve the spectrum whose `SCANS` entry includes `scan_number`
+    pub fn get_spectrum_by_scan_number(
+        &mut self,
+        scan_number: u64,
+    ) -> Option<MultiLayerSpectrum<C, D>> {
+        let index = self.index_of_scan_number(scan_number)?;
+        self.get_spectrum_by_index(index)
+    }
 }
 
 impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting>
@@ -710,8 +840,12 @@ impl<R: SeekRead, C: CentroidPeakAdapting, D: DeconvolutedPeakAdapting>
         &self.index
     }
 
+    /// Replace the spectrum offset index, e.g. with one read by [`MZFileReader::read_index`].
+    /// The reader's [`DuplicateIdPolicy`] is kept, not the one stored with `index`.
     fn set_index(&mut self, index: OffsetIndex) {
+        let policy = self.index.duplicate_id_policy;
         self.index = index;
+        self.index.duplicate_id_policy = policy;
     }
 }
 
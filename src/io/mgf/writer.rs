@@ -111,11 +111,14 @@ impl MGFHeaderStyle for MZDataMGFStyle {
         let desc = spectrum.description();
         writer.write_kv("NATIVEID", spectrum.id())?;
         writer.write_kv("SCANS", &desc.index.to_string())?;
-        for param in desc
-            .params()
-            .iter()
-            .filter(|p| TITLE_CV != **p && MSN_SPECTRUM_CV != **p && MS_LEVEL_CV != **p)
-        {
+        // `first scan` and `last scan` are derived from a `SCANS` range when reading, so they
+        // are not written back as headers of their own
+        for param in desc.params().iter().filter(|p| {
+            TITLE_CV != **p
+                && MSN_SPECTRUM_CV != **p
+                && MS_LEVEL_CV != **p
+                && !matches!(p.name.as_str(), "first scan" | "last scan")
+        }) {
             writer.write_param(param)?;
         }
         Ok(())
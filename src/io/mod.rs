@@ -11,6 +11,7 @@ pub mod mzmlb;
 mod offset_index;
 #[cfg(feature = "proxi")]
 pub mod proxi;
+mod report;
 mod shorthand;
 pub(crate) mod traits;
 mod utils;
@@ -28,19 +29,22 @@ pub use crate::io::mzml::AsyncMzMLReader;
 pub use crate::io::mzml::{MzMLParserError, MzMLReader, MzMLWriter};
 #[cfg(feature = "mzmlb")]
 pub use crate::io::mzmlb::{MzMLbError, MzMLbReader};
-pub use crate::io::offset_index::OffsetIndex;
+pub use crate::io::offset_index::{DuplicateIdError, DuplicateIdPolicy, OffsetIndex};
+pub use crate::io::report::{quick_report, AcquisitionStyle, FileReport};
 pub use crate::io::traits::{
     BorrowedGeneric3DIonMobilityFrameSource, ChromatogramIterator, ChromatogramSource,
-    Generic3DIonMobilityFrameSource, IonMobilityFrameAccessError, IonMobilityFrameGrouping,
+    DynSpectrumSource, Generic3DIonMobilityFrameSource, IonMobilityFrameAccessError, IonMobilityFrameGrouping,
     IonMobilityFrameIterator, IonMobilityFrameSource, MZFileReader, MemorySpectrumSource,
     RandomAccessIonMobilityFrameIterator, RandomAccessSpectrumGroupingIterator,
-    RandomAccessSpectrumIterator, RandomAccessSpectrumSource, SpectrumAccessError,
-    SpectrumGrouping, SpectrumIterator, SpectrumReceiver, SpectrumSource,
+    RandomAccessSpectrumIterator, RandomAccessSpectrumSource, ReverseTimeSpectrumIterator,
+    SpectrumAccessError, SpectrumGrouping, SpectrumIterator, SpectrumReceiver, SpectrumSource,
     SpectrumSourceWithMetadata, SpectrumWriter, StreamingSpectrumIterator,
     RandomAccessIonMobilityFrameGroupingIterator,
 };
-pub use crate::io::utils::{checksum_file, DetailLevel, PreBufferedStream};
-pub use compression::RestartableGzDecoder;
+pub use crate::io::utils::{
+    checksum_file, DetailLevel, HashAlgorithm, HashingReader, HashingWriter, PreBufferedStream,
+};
+pub use compression::{FileCompression, RestartableDecoder, RestartableGzDecoder};
 
 #[cfg(any(feature = "thermo", feature="doc-only"))]
 pub mod thermo;
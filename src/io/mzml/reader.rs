@@ -6,7 +6,7 @@ use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::marker::PhantomData;
 use std::mem;
 
-use log::{debug, trace, warn};
+use log::{debug, error, trace, warn};
 
 use mzpeaks::CentroidLike;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
@@ -17,7 +17,7 @@ use crate::meta::DissociationEnergyTerm;
 use crate::meta::Sample;
 use crate::prelude::*;
 
-use super::super::offset_index::OffsetIndex;
+use super::super::offset_index::{DuplicateIdError, DuplicateIdPolicy, OffsetIndex};
 use super::super::traits::{
     ChromatogramSource, MZFileReader, RandomAccessSpectrumIterator, SeekRead, SpectrumAccessError,
     SpectrumSource,
@@ -131,6 +131,7 @@ pub trait SpectrumBuilding<
                 1000515 => self.current_array_mut().name = ArrayType::IntensityArray,
                 1000516 => self.current_array_mut().name = ArrayType::ChargeArray,
                 1000517 => self.current_array_mut().name = ArrayType::SignalToNoiseArray,
+                1002530 => self.current_array_mut().name = ArrayType::BaselineArray,
                 1000786 => {
                     self.current_array_mut().name = ArrayType::NonStandardDataArray {
                         name: Box::new(param.value().to_string()),
@@ -330,8 +331,13 @@ pub struct MzMLSpectrumBuilder<
     pub signal_continuity: SignalContinuity,
     pub has_precursor: bool,
     pub detail_level: DetailLevel,
+    /// Whether to try other compression methods when an array fails to decode
+    /// with the one it declares. See [`DataArray::decode_and_store_lenient`].
+    pub lenient_compression: bool,
     pub instrument_id_map: Option<&'a mut IncrementingIdMap>,
     entry_type: EntryType,
+    default_array_length: Option<usize>,
+    current_array_length: Option<usize>,
     centroid_type: PhantomData<C>,
     deconvoluted_type: PhantomData<D>,
 }
@@ -508,6 +514,8 @@ impl<
         self.has_precursor = false;
         self.signal_continuity = SignalContinuity::Unknown;
         self.polarity = ScanPolarity::Unknown;
+        self.default_array_length = None;
+        self.current_array_length = None;
     }
 
     pub fn set_entry_type(&mut self, entry_type: EntryType) {
@@ -592,8 +600,20 @@ impl<
                 } else {
                     match param.name.as_ref() {
                         "collision energy" | "activation energy" => {
-                            self.precursor.activation.energy =
-                                param.to_f32().expect("Failed to parse collision energy");
+                            let energy = param.to_f32().expect("Failed to parse collision energy");
+                            let unit = match param.unit {
+                                Unit::Unknown => Unit::Electronvolt,
+                                unit => unit,
+                            };
+                            self.precursor.activation.set_energy_with_unit(energy, unit);
+                        }
+                        "normalized collision energy" => {
+                            let energy = param.to_f32().expect("Failed to parse collision energy");
+                            let unit = match param.unit {
+                                Unit::Unknown => Unit::PercentElectronVolt,
+                                unit => unit,
+                            };
+                            self.precursor.activation.set_energy_with_unit(energy, unit);
                         }
                         &_ => {
                             self.precursor.activation.add_param(param);
@@ -645,6 +665,10 @@ impl<
                                     .parse::<usize>()
                                     .expect("Failed to parse index");
                             }
+                            b"defaultArrayLength" => {
+                                self.default_array_length =
+                                    String::from_utf8_lossy(&attr.value).parse::<usize>().ok();
+                            }
                             _ => {}
                         },
                         Err(msg) => {
@@ -736,6 +760,19 @@ impl<
                 return Ok(MzMLParserState::BinaryDataArrayList);
             }
             b"binaryDataArray" => {
+                for attr_parsed in event.attributes() {
+                    match attr_parsed {
+                        Ok(attr) => {
+                            if attr.key.as_ref() == b"arrayLength" {
+                                self.current_array_length =
+                                    String::from_utf8_lossy(&attr.value).parse::<usize>().ok();
+                            }
+                        }
+                        Err(msg) => {
+                            return Err(self.handle_xml_error(msg.into(), state));
+                        }
+                    }
+                }
                 return Ok(MzMLParserState::BinaryDataArray);
             }
             b"binary" => {
@@ -758,6 +795,10 @@ impl<
                                     .parse::<usize>()
                                     .expect("Failed to parse index");
                             }
+                            b"defaultArrayLength" => {
+                                self.default_array_length =
+                                    String::from_utf8_lossy(&attr.value).parse::<usize>().ok();
+                            }
                             _ => {}
                         },
                         Err(msg) => {
@@ -868,7 +909,13 @@ impl<
                                                         self.warning_context()
                                                     );
                                                 }
-                                                self.precursor.activation.energy = t.energy();
+                                                let unit = match param.unit() {
+                                                    Unit::Unknown => t.default_unit(),
+                                                    unit => unit,
+                                                };
+                                                self.precursor
+                                                    .activation
+                                                    .set_energy_with_unit(t.energy(), unit);
                                             }
                                         }
                                         None => {
@@ -915,8 +962,21 @@ impl<
             }
             b"binaryDataArray" => {
                 let mut array = mem::take(&mut self.current_array);
-                if self.detail_level == DetailLevel::Full {
-                    array.decode_and_store().map_err(|e| {
+                let array_length = self.current_array_length.take().or(self.default_array_length);
+                // A mislabeled array can only be detected by decoding it, so lenient decoding
+                // is done eagerly even when the detail level is `Lazy`
+                let decode_now = match self.detail_level {
+                    DetailLevel::Full => true,
+                    DetailLevel::Lazy => self.lenient_compression,
+                    DetailLevel::MetadataOnly => false,
+                };
+                if decode_now {
+                    let decoded = if self.lenient_compression {
+                        array.decode_and_store_lenient(array_length)
+                    } else {
+                        array.decode_and_store()
+                    };
+                    decoded.map_err(|e| {
                         MzMLParserError::ArrayDecodingError(state, array.name.clone(), e)
                     })?;
                 }
@@ -1011,6 +1071,7 @@ pub struct MzMLReaderType<
     /// A cache of repeated paramters
     pub reference_param_groups: HashMap<String, Vec<Param>>,
     pub detail_level: DetailLevel,
+    lenient_compression: bool,
 
     // SpectrumList attributes
     pub run: MassSpectrometryRun,
@@ -1060,6 +1121,7 @@ impl<
             data_processings: Vec::new(),
             reference_param_groups: HashMap::new(),
             detail_level,
+            lenient_compression: false,
 
             centroid_type: PhantomData,
             deconvoluted_type: PhantomData,
@@ -1074,6 +1136,40 @@ impl<
         inst
     }
 
+    /// Whether arrays that fail to decode with their declared compression method will be
+    /// retried with other plausible methods.
+    pub fn lenient_compression(&self) -> bool {
+        self.lenient_compression
+    }
+
+    /// Enable or disable lenient decoding of binary data arrays.
+    ///
+    /// Some tools mislabel the compression method of their arrays, e.g. declaring zlib
+    /// compression for uncompressed bytes. When enabled, an array that fails to decode, or
+    /// decodes to a length other than the entry's `defaultArrayLength`, will be retried with
+    /// the other plausible compression methods, logging a warning if one succeeds. See
+    /// [`DataArray::decode_and_store_lenient`].
+    ///
+    /// # Note
+    /// A mislabeled array can only be detected by decoding it, so when this is enabled arrays
+    /// are decoded eagerly even if the detail level is [`DetailLevel::Lazy`]. This has no effect
+    /// with [`DetailLevel::MetadataOnly`], where arrays are not read at all.
+    pub fn set_lenient_compression(&mut self, lenient_compression: bool) {
+        self.lenient_compression = lenient_compression;
+    }
+
+    /// How duplicate spectrum and chromatogram IDs are handled when building the offset indices
+    pub fn duplicate_id_policy(&self) -> DuplicateIdPolicy {
+        self.spectrum_index.duplicate_id_policy
+    }
+
+    /// Check the offset indices for any duplicate IDs encountered while building them.
+    /// See [`OffsetIndex::validate`].
+    pub fn validate(&self) -> Result<(), DuplicateIdError> {
+        self.spectrum_index.validate()?;
+        self.chromatogram_index.validate()
+    }
+
     /**Parse the metadata section of the file using [`FileMetadataBuilder`]
      */
     fn parse_metadata(&mut self) -> Result<(), MzMLParserError> {
@@ -1340,7 +1436,8 @@ impl<
         &mut self,
         spectrum: &mut MultiLayerSpectrum<C, D>,
     ) -> Result<usize, MzMLParserError> {
-        let accumulator = MzMLSpectrumBuilder::<C, D>::with_detail_level(self.detail_level);
+        let mut accumulator = MzMLSpectrumBuilder::<C, D>::with_detail_level(self.detail_level);
+        accumulator.lenient_compression = self.lenient_compression;
         match self.state {
             MzMLParserState::SpectrumDone => {
                 self.state = MzMLParserState::Resume;
@@ -1386,7 +1483,8 @@ impl<
     }
 
     fn _read_next_chromatogram(&mut self) -> Result<Chromatogram, MzMLParserError> {
-        let accumulator = MzMLSpectrumBuilder::<C, D>::with_detail_level(self.detail_level);
+        let mut accumulator = MzMLSpectrumBuilder::<C, D>::with_detail_level(self.detail_level);
+        accumulator.lenient_compression = self.lenient_compression;
 
         match self.state {
             MzMLParserState::ChromatogramDone => {
@@ -1634,8 +1732,12 @@ impl<
         &self.spectrum_index
     }
 
+    /// Replace the spectrum offset index, e.g. with one read by [`MZFileReader::read_index`].
+    /// The reader's [`DuplicateIdPolicy`] is kept, not the one stored with `index`.
     fn set_index(&mut self, index: OffsetIndex) {
-        self.spectrum_index = index
+        let policy = self.duplicate_id_policy();
+        self.spectrum_index = index;
+        self.spectrum_index.duplicate_id_policy = policy;
     }
 
     fn detail_level(&self) -> &DetailLevel {
@@ -1703,6 +1805,39 @@ impl<
         reader
     }
 
+    /// Construct a new MzMLReaderType and build an offset index like [`Self::new_indexed`],
+    /// handling duplicate IDs according to `policy`.
+    pub fn new_indexed_with_duplicate_id_policy(
+        file: R,
+        policy: DuplicateIdPolicy,
+    ) -> MzMLReaderType<R, C, D> {
+        let mut reader = Self::with_buffer_capacity_and_detail_level(file, BUFFER_SIZE, DetailLevel::Full);
+        reader.spectrum_index.duplicate_id_policy = policy;
+        reader.chromatogram_index.duplicate_id_policy = policy;
+        reader._read_index();
+        reader
+    }
+
+    /// Set how duplicate spectrum and chromatogram IDs are handled when building the offset
+    /// indices. If the reader has already been indexed, e.g. by [`MZFileReader::open_path`],
+    /// the index is rebuilt from the stream with the new policy.
+    ///
+    /// A [`DuplicateIdPolicy::Error`] cannot fail here. Instead the duplicates are left out of
+    /// the index, and [`MzMLReaderType::validate`] reports them.
+    pub fn set_duplicate_id_policy(&mut self, policy: DuplicateIdPolicy) {
+        let changed = policy != self.duplicate_id_policy();
+        self.spectrum_index.duplicate_id_policy = policy;
+        self.chromatogram_index.duplicate_id_policy = policy;
+        if changed && self.spectrum_index.init {
+            let position = self
+                .stream_position()
+                .expect("Failed to save restore location");
+            self._read_index();
+            self.seek(SeekFrom::Start(position))
+                .expect("Failed to restore location");
+        }
+    }
+
     fn _read_index(&mut self) {
         if let Err(err) = self.read_index_from_end() {
             debug!("Failed to read index from the end of the file: {}", err);
@@ -1765,6 +1900,9 @@ impl<
     /// though this index may be malformed in some older files.
     pub fn read_index_from_end(&mut self) -> Result<u64, MzMLIndexingError> {
         let mut indexer = IndexedMzMLIndexExtractor::new();
+        let policy = self.duplicate_id_policy();
+        indexer.spectrum_index.duplicate_id_policy = policy;
+        indexer.chromatogram_index.duplicate_id_policy = policy;
         let current_position = match self.handle.stream_position() {
             Ok(position) => position,
             Err(err) => return Err(MzMLIndexingError::IOError(err)),
@@ -1824,6 +1962,10 @@ impl<
             }
         }
         self.buffer.clear();
+        if matches!(policy, DuplicateIdPolicy::Error) {
+            indexer.spectrum_index.validate()?;
+            indexer.chromatogram_index.validate()?;
+        }
         self.spectrum_index = indexer.spectrum_index;
         self.spectrum_index.init = true;
         *self.chromatogram_index = indexer.chromatogram_index;
@@ -1834,6 +1976,9 @@ impl<
 
     /// Builds an offset index to each `<spectrum>` XML element
     /// by doing a fast pre-scan of the XML file.
+    ///
+    /// Duplicate IDs never make this fail. Under [`DuplicateIdPolicy::Error`] they are left out
+    /// of the index and logged, and [`MzMLReaderType::validate`] reports them.
     pub fn build_index(&mut self) -> u64 {
         let start = self
             .handle
@@ -1841,6 +1986,9 @@ impl<
             .expect("Failed to save restore location");
         self.seek(SeekFrom::Start(0))
             .expect("Failed to reset stream to beginning");
+        let policy = self.duplicate_id_policy();
+        self.spectrum_index = OffsetIndex::new("spectrum".to_owned());
+        self.spectrum_index.duplicate_id_policy = policy;
         let mut reader = Reader::from_reader(&mut self.handle);
         reader.trim_text(true);
         loop {
@@ -1895,6 +2043,11 @@ impl<
         if self.spectrum_index.is_empty() {
             warn!("An index was built but no entries were found")
         }
+        if matches!(policy, DuplicateIdPolicy::Error) {
+            if let Err(err) = self.spectrum_index.validate() {
+                error!("{err}, the duplicates were left out of the index");
+            }
+        }
         offset
     }
 }
@@ -1909,10 +2062,9 @@ impl<
     }
 
     fn construct_index_from_stream(&mut self) -> u64 {
-        if let Ok(count) = self.read_index_from_end() {
-            count
-        } else {
-            self.build_index()
+        match self.read_index_from_end() {
+            Ok(count) => count,
+            Err(_) => self.build_index(),
         }
     }
 }
@@ -2283,6 +2435,170 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_lenient_compression() -> io::Result<()> {
+        let reference: Vec<_> = MzMLReader::open_path("./test/data/small.mzML")?.collect();
+
+        // Declare the uncompressed arrays as zlib compressed
+        let content = fs::read_to_string("./test/data/small.mzML")?.replace(
+            r#"accession="MS:1000576" name="no compression""#,
+            r#"accession="MS:1000574" name="zlib compression""#,
+        );
+        let mut reader = MzMLReader::new(io::Cursor::new(content.into_bytes()));
+        assert!(!reader.lenient_compression());
+        reader.set_lenient_compression(true);
+
+        let mut n = 0;
+        for (expected, scan) in reference.iter().zip(reader.by_ref()) {
+            assert_eq!(expected.id(), scan.id());
+            assert_eq!(expected.peaks().len(), scan.peaks().len());
+            n += 1;
+        }
+        assert_eq!(n, reference.len());
+
+        // Lazily loaded arrays are decoded eagerly so that the mislabeling is caught up front
+        reader.reset();
+        reader.set_detail_level(DetailLevel::Lazy);
+        let scan = reader.next().unwrap();
+        let arrays = scan.arrays.as_ref().unwrap();
+        assert!(arrays
+            .iter()
+            .all(|(_, a)| a.compression == BinaryCompressionType::Decoded));
+        assert_eq!(arrays.mzs()?.len(), reference[0].peaks().len());
+
+        // A wrong `defaultArrayLength` with a correctly declared compression is kept as decoded
+        let content = fs::read_to_string("./test/data/small.mzML")?
+            .replacen(r#"defaultArrayLength="19800""#, r#"defaultArrayLength="19799""#, 1);
+        let mut reader = MzMLReader::new(io::Cursor::new(content.into_bytes()));
+        reader.set_lenient_compression(true);
+        let scans: Vec<_> = reader.collect();
+        assert_eq!(scans.len(), reference.len());
+        assert_eq!(scans[1].arrays.as_ref().unwrap().mzs()?.len(), 19800);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_empty_spectra() -> io::Result<()> {
+        let mut reader = MzMLReader::open_path("./test/data/empty_spectra.mzML")?;
+        assert_eq!(reader.len(), 3);
+
+        let mut scans: Vec<_> = reader.iter().collect();
+        assert_eq!(scans.len(), 3);
+        assert_eq!(scans[0].peaks().len(), 3);
+
+        for scan in scans.iter_mut().skip(1) {
+            assert_eq!(scan.ms_level(), 2);
+            let arrays = scan.arrays.as_ref().unwrap();
+            assert_eq!(arrays.mzs()?.len(), 0);
+            assert_eq!(arrays.intensities()?.len(), 0);
+            assert_eq!(scan.peaks().len(), 0);
+
+            let peaks = scan.try_build_peaks().unwrap();
+            assert!(matches!(peaks, crate::spectrum::RefPeakDataLevel::Centroid(_)));
+            assert_eq!(peaks.len(), 0);
+            scan.update_summaries();
+        }
+
+        let mut buffer = Vec::new();
+        {
+            let mut writer = crate::io::mzml::MzMLWriter::new(&mut buffer);
+            writer.copy_metadata_from(&reader);
+            writer.set_spectrum_count_hint(Some(scans.len() as u64));
+            for scan in scans.iter() {
+                writer.write(scan)?;
+            }
+            writer.close()?;
+        }
+
+        // Empty arrays are written with an empty `<binary>` element
+        let text = String::from_utf8_lossy(&buffer);
+        assert_eq!(text.matches(r#"defaultArrayLength="0""#).count(), 2);
+        assert_eq!(text.matches(r#"encodedLength="0""#).count(), 4);
+
+        let reader = MzMLReader::new(io::Cursor::new(buffer));
+        let reread: Vec<_> = reader.collect();
+        assert_eq!(reread.len(), 3);
+        for (expected, scan) in scans.iter().zip(reread.iter()) {
+            assert_eq!(expected.id(), scan.id());
+            assert_eq!(expected.peaks().len(), scan.peaks().len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_id_policy() -> io::Result<()> {
+        // Rename the second spectrum to collide with the first without changing any offsets
+        let content = fs::read_to_string("./test/data/small.mzML")?
+            .replace(r#"scan=2""#, r#"scan=1""#)
+            .into_bytes();
+        let first_id = "controllerType=0 controllerNumber=1 scan=1";
+        let mut reference = MzMLReader::open_path("./test/data/small.mzML")?;
+        let first_time = reference.get_spectrum_by_index(0).unwrap().start_time();
+        let second_time = reference.get_spectrum_by_index(1).unwrap().start_time();
+
+        let mut reader = MzMLReader::new(io::Cursor::new(content.clone()));
+        assert_eq!(reader.duplicate_id_policy(), DuplicateIdPolicy::KeepLast);
+        reader.read_index_from_end()?;
+        assert_eq!(reader.len(), 47);
+        assert_eq!(reader.get_spectrum_by_id(first_id).unwrap().start_time(), second_time);
+        let err = reader.validate().unwrap_err();
+        assert_eq!(err.id, first_id);
+        assert_eq!(err.count, 1);
+
+        let mut reader = MzMLReader::new(io::Cursor::new(content.clone()));
+        reader.set_duplicate_id_policy(DuplicateIdPolicy::KeepFirst);
+        reader.build_index();
+        assert_eq!(reader.len(), 47);
+        assert_eq!(reader.get_spectrum_by_id(first_id).unwrap().start_time(), first_time);
+        assert!(reader.validate().is_err());
+
+        let mut reader = MzMLReader::new(io::Cursor::new(content.clone()));
+        reader.set_duplicate_id_policy(DuplicateIdPolicy::MakeUnique);
+        reader.read_index_from_end()?;
+        assert_eq!(reader.len(), 48);
+        let unique_id = format!("{first_id}_1");
+        assert_eq!(reader.spectrum_index.index_of(&unique_id), Some(1));
+        let scan = reader.get_spectrum_by_index(1).unwrap();
+        assert_eq!(scan.start_time(), second_time);
+
+        let mut reader = MzMLReader::new(io::Cursor::new(content.clone()));
+        reader.set_duplicate_id_policy(DuplicateIdPolicy::Error);
+        assert!(matches!(
+            reader.read_index_from_end(),
+            Err(MzMLIndexingError::DuplicateId(_))
+        ));
+
+        // Changing the policy of an indexed reader rebuilds the index
+        let mut reader = MzMLReader::new_indexed(io::Cursor::new(content.clone()));
+        assert_eq!(reader.get_spectrum_by_id(first_id).unwrap().start_time(), second_time);
+        reader.set_duplicate_id_policy(DuplicateIdPolicy::KeepFirst);
+        assert_eq!(reader.get_spectrum_by_id(first_id).unwrap().start_time(), first_time);
+
+        // The erroring policy falls back to an index without the duplicates
+        let mut reader = MzMLReader::new_indexed_with_duplicate_id_policy(
+            io::Cursor::new(content.clone()),
+            DuplicateIdPolicy::Error,
+        );
+        assert_eq!(reader.len(), 47);
+        assert_eq!(reader.get_spectrum_by_id(first_id).unwrap().start_time(), first_time);
+        assert!(reader.validate().is_err());
+
+        // A serialized index does not override the reader's policy
+        let mut buffer = Vec::new();
+        reader.spectrum_index.to_writer(&mut buffer)?;
+        let mut reader = MzMLReader::new_indexed_with_duplicate_id_policy(
+            io::Cursor::new(content),
+            DuplicateIdPolicy::MakeUnique,
+        );
+        assert_eq!(reader.len(), 48);
+        reader.set_index(OffsetIndex::from_reader(io::Cursor::new(buffer))?);
+        assert_eq!(reader.len(), 47);
+        assert_eq!(reader.duplicate_id_policy(), DuplicateIdPolicy::MakeUnique);
+
+        assert!(reference.validate().is_ok());
+        Ok(())
+    }
+
     #[test]
     fn test_random_access_iterator() -> io::Result<()> {
         let path = path::Path::new("./test/data/small.mzML");
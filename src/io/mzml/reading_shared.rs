@@ -12,7 +12,7 @@ use thiserror::Error;
 
 use super::reader::Bytes;
 use crate::io::traits::SeekRead;
-use crate::io::OffsetIndex;
+use crate::io::{DuplicateIdError, OffsetIndex};
 use crate::meta::{
     Component, ComponentType, DataProcessing, FileDescription, InstrumentConfiguration,
     MassSpectrometerFileFormatTerm, NativeSpectrumIdentifierFormatTerm, ProcessingMethod, Sample,
@@ -371,6 +371,12 @@ pub enum MzMLIndexingError {
         #[source]
         io::Error,
     ),
+    #[error("{0}")]
+    DuplicateId(
+        #[from]
+        #[source]
+        DuplicateIdError,
+    ),
 }
 
 impl From<MzMLIndexingError> for io::Error {
@@ -382,6 +388,7 @@ impl From<MzMLIndexingError> for io::Error {
                 _ => io::Error::new(io::ErrorKind::InvalidData, e),
             },
             MzMLIndexingError::IOError(e) => e,
+            MzMLIndexingError::DuplicateId(e) => io::Error::new(io::ErrorKind::InvalidData, e),
         }
     }
 }
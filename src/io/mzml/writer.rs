@@ -16,8 +16,8 @@ use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Error as XMLError, Writer};
 
 use super::super::offset_index::OffsetIndex;
-use super::super::traits::SpectrumWriter;
-use super::super::utils::MD5HashingStream;
+use super::super::traits::{SpectrumReceiver, SpectrumWriter};
+use super::super::utils::{HashAlgorithm, HashingWriter};
 
 use mzpeaks::{CentroidPeak, DeconvolutedPeak};
 
@@ -191,12 +191,12 @@ impl From<MzMLWriterError> for io::Error {
 pub type WriterResult = Result<(), MzMLWriterError>;
 
 struct ByteCountingStream<W: io::Write> {
-    stream: BufWriter<MD5HashingStream<W>>,
+    stream: BufWriter<HashingWriter<W>>,
     bytes_written: u64,
 }
 
 impl<W: io::Write> ByteCountingStream<W> {
-    pub fn new(stream: BufWriter<MD5HashingStream<W>>) -> Self {
+    pub fn new(stream: BufWriter<HashingWriter<W>>) -> Self {
         Self {
             stream,
             bytes_written: 0,
@@ -207,8 +207,8 @@ impl<W: io::Write> ByteCountingStream<W> {
         self.bytes_written
     }
 
-    pub fn checksum(&self) -> md5::Digest {
-        self.stream.get_ref().compute()
+    pub fn checksum(&self) -> String {
+        self.stream.get_ref().hexdigest()
     }
 
     pub fn get_mut(&mut self) -> &mut W {
@@ -246,7 +246,7 @@ impl<W: io::Write> InnerXMLWriter<W> {
     pub fn new(file: W) -> InnerXMLWriter<W> {
         let handle = ByteCountingStream::new(BufWriter::with_capacity(
             BUFFER_SIZE,
-            MD5HashingStream::new(file),
+            HashingWriter::new(file, HashAlgorithm::MD5),
         ));
         Self {
             handle: Writer::new_with_indent(handle, b' ', 2),
@@ -254,8 +254,7 @@ impl<W: io::Write> InnerXMLWriter<W> {
     }
 
     pub fn digest(&mut self) -> String {
-        let digest = self.handle.get_ref().checksum();
-        format!("{:x}", digest)
+        self.handle.get_ref().checksum()
     }
 
     pub fn flush(&mut self) -> io::Result<()> {
@@ -1309,6 +1308,37 @@ where
             Ok(())
         }
     }
+
+    /**
+    Write every spectrum received from `rx` until all of its senders have hung up,
+    and then [`close`](MzMLWriterType::close) the document. Returns the number of
+    spectra written.
+
+    This is the consuming half of a producer/consumer pipeline where another thread
+    reads spectra and sends them over a channel. The metadata carried by `rx` is not
+    copied automatically, call [`MSDataFileMetadata::copy_metadata_from`] before this
+    method if it should be written to the header.
+
+    # Errors
+    This function will return an error if a [`MzMLWriterError`] error occurs during
+    writing any spectrum or closing the document.
+    */
+    pub fn write_from_receiver<
+        C1: CentroidLike + Default + BuildArrayMapFrom + Send,
+        D1: DeconvolutedCentroidLike + Default + BuildArrayMapFrom + Send,
+        S: SpectrumLike<C1, D1> + Send + 'static,
+    >(
+        &mut self,
+        rx: SpectrumReceiver<C1, D1, S>,
+    ) -> Result<usize, MzMLWriterError> {
+        let mut count = 0;
+        for spectrum in rx {
+            self.write_spectrum(&spectrum)?;
+            count += 1;
+        }
+        self.close()?;
+        Ok(count)
+    }
 }
 
 impl<W: Write, C: CentroidLike + Default, D: DeconvolutedCentroidLike + Default>
@@ -1469,12 +1499,21 @@ where
             self.handle.write_param(&meth_param)?;
         }
         self.handle.write_param_list(act.params().iter())?;
-        self.handle.write_param(
-            &self
+        let energy_param = match act.energy_with_unit() {
+            (energy, Unit::PercentElectronVolt) => self
                 .ms_cv
-                .param_val("MS:1000045", "collision energy", act.energy)
-                .with_unit("UO:0000266", "electronvolt"),
-        )?;
+                .param_val("MS:1000138", "normalized collision energy", energy)
+                .with_unit_t(&Unit::PercentElectronVolt),
+            (energy, Unit::Unknown) => self
+                .ms_cv
+                .param_val("MS:1000045", "collision energy", energy)
+                .with_unit_t(&Unit::Electronvolt),
+            (energy, unit) => self
+                .ms_cv
+                .param_val("MS:1000045", "collision energy", energy)
+                .with_unit_t(&unit),
+        };
+        self.handle.write_param(&energy_param)?;
         end_event!(self, tag);
         Ok(())
     }
@@ -1650,9 +1689,11 @@ where
         )?;
 
         match &array.name {
-            ArrayType::MZArray | ArrayType::IntensityArray | ArrayType::ChargeArray => {
-                self.handle.write_param(&array.name.as_param_const())?
-            }
+            ArrayType::MZArray
+            | ArrayType::IntensityArray
+            | ArrayType::ChargeArray
+            | ArrayType::SignalToNoiseArray
+            | ArrayType::BaselineArray => self.handle.write_param(&array.name.as_param_const())?,
             ArrayType::TimeArray
             | ArrayType::RawIonMobilityArray
             | ArrayType::MeanIonMobilityArray
@@ -2194,4 +2235,58 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_from_receiver() -> WriterResult {
+        let tmpdir = tempfile::tempdir()?;
+        let dest_path = tmpdir.path().join("from_receiver.mzML");
+        let reader = MzMLReader::open_path("./test/data/small.mzML")?;
+
+        let (send, recv) = std::sync::mpsc::sync_channel(16);
+        let rx: SpectrumReceiver<_, _, _> = recv.into();
+        let mut writer = MzMLWriter::new(fs::File::create(&dest_path)?);
+        writer.copy_metadata_from(&reader);
+
+        let handle = std::thread::spawn(move || {
+            reader.for_each(|s| send.send(s).unwrap());
+        });
+        let n = writer.write_from_receiver(rx)?;
+        handle.join().unwrap();
+        assert_eq!(n, 48);
+        assert_eq!(writer.state, MzMLWriterState::End);
+
+        let mut reader2 = MzMLReader::open_path(dest_path)?;
+        assert_eq!(reader2.len(), 48);
+        assert_eq!(reader2.get_spectrum_by_index(47).unwrap().index(), 47);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_energy_unit() -> WriterResult {
+        let mut reader = MzMLReader::open_path("./test/data/small.mzML")?;
+        let mut spectrum = reader.iter().find(|s| s.ms_level() == 2).unwrap();
+        let activation = &spectrum.precursor().unwrap().activation;
+        assert_eq!(activation.energy_with_unit(), (35.0, Unit::Electronvolt));
+
+        spectrum
+            .precursor_mut()
+            .unwrap()
+            .activation
+            .set_energy_with_unit(28.0, Unit::PercentElectronVolt);
+
+        let mut writer = MzMLWriter::new(io::Cursor::new(Vec::new()));
+        writer.write_spectrum(&spectrum)?;
+        writer.close()?;
+        let buffer = writer.get_mut()?.get_ref().clone();
+        let text = String::from_utf8_lossy(&buffer);
+        assert!(text.contains(r#"name="normalized collision energy" value="28""#));
+
+        let mut reader = MzMLReader::new(io::Cursor::new(buffer));
+        let dup = reader.next().unwrap();
+        let activation = &dup.precursor().unwrap().activation;
+        let (energy, unit) = activation.energy_with_unit();
+        assert_eq!((energy, unit), (28.0, Unit::PercentElectronVolt));
+        assert!(activation.get_param_by_name("normalized collision energy").is_none());
+        Ok(())
+    }
 }
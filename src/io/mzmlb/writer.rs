@@ -190,6 +190,7 @@ impl ToString for BufferName {
             ArrayType::IntensityArray => Cow::Borrowed("intensity"),
             ArrayType::ChargeArray => Cow::Borrowed("charge"),
             ArrayType::SignalToNoiseArray => Cow::Borrowed("snr"),
+            ArrayType::BaselineArray => Cow::Borrowed("baseline"),
             ArrayType::TimeArray => Cow::Borrowed("time"),
             ArrayType::WavelengthArray => Cow::Borrowed("wavelength"),
             ArrayType::IonMobilityArray => Cow::Borrowed("ion_mobility"),
@@ -614,6 +615,9 @@ where
             ArrayType::SignalToNoiseArray => {
                 self.mzml_writer.write_param(&array.name.as_param_const())?
             }
+            ArrayType::BaselineArray => {
+                self.mzml_writer.write_param(&array.name.as_param_const())?
+            }
             ArrayType::NonStandardDataArray { name } => {
                 let mut p =
                     self.get_ms_cv()
@@ -2,11 +2,44 @@ use std::io::prelude::*;
 
 use serde::{Deserialize, Serialize};
 use serde_json;
+use thiserror::Error;
 
 use indexmap::map::{Iter, Keys};
 use indexmap::IndexMap;
 
 
+/// How an [`OffsetIndex`] handles an ID that is already present, as can happen when a
+/// file was produced by incorrectly merging several runs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DuplicateIdPolicy {
+    /// Treat a duplicate as an error. The duplicate is not indexed and readers that can
+    /// fail while indexing will do so.
+    Error,
+    /// Keep the first occurrence of the ID and ignore later ones
+    KeepFirst,
+    /// Keep the offset of the last occurrence of the ID, at the position of the first
+    /// occurrence in the index
+    #[default]
+    KeepLast,
+    /// Index each later occurrence under a new ID made unique by appending a numeric
+    /// suffix, e.g. `scan=1` becomes `scan=1_1`
+    MakeUnique,
+}
+
+/// An error describing a duplicate ID found while building an [`OffsetIndex`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Found {count} duplicate ID(s) in the {name} index, first {id:?} at offset {offset}")]
+pub struct DuplicateIdError {
+    /// The name of the index the duplicates were found in
+    pub name: String,
+    /// The first duplicated ID
+    pub id: String,
+    /// The offset of the first duplicated ID's repeat occurrence
+    pub offset: u64,
+    /// The total number of duplicate occurrences
+    pub count: usize,
+}
+
 /**
 An ordered mapping from entity ID to byte offset into the source
 file it resides in.
@@ -27,6 +60,14 @@ pub struct OffsetIndex {
     /// Whether the index has been initalized explicitly or not, as
     /// it may be initially empty or read as empty.
     pub init: bool,
+
+    /// How to handle an ID that is inserted more than once
+    #[serde(default)]
+    pub duplicate_id_policy: DuplicateIdPolicy,
+
+    /// Every repeated ID and offset encountered by [`OffsetIndex::insert`]
+    #[serde(skip)]
+    pub duplicates: Vec<(Box<str>, u64)>,
 }
 
 impl OffsetIndex {
@@ -59,10 +100,50 @@ impl OffsetIndex {
         self.offsets.get_index_of(key)
     }
 
-    /// Insert `key` into the index with an offset value
-    #[inline]
+    /// Insert `key` into the index with an offset value.
+    ///
+    /// If `key` is already present, the occurrence is recorded in [`OffsetIndex::duplicates`]
+    /// and handled according to [`OffsetIndex::duplicate_id_policy`]. Returns the offset
+    /// previously associated with `key`, if any.
     pub fn insert<T: Into<Box<str>>>(&mut self, key: T, offset: u64) -> Option<u64> {
-        self.offsets.insert(key.into(), offset)
+        let key: Box<str> = key.into();
+        let Some(prev) = self.offsets.get(&key).copied() else {
+            self.offsets.insert(key, offset);
+            return None;
+        };
+        self.duplicates.push((key.clone(), offset));
+        match self.duplicate_id_policy {
+            DuplicateIdPolicy::Error | DuplicateIdPolicy::KeepFirst => {}
+            DuplicateIdPolicy::KeepLast => {
+                self.offsets.insert(key, offset);
+            }
+            DuplicateIdPolicy::MakeUnique => {
+                let mut i = 1;
+                let unique = loop {
+                    let candidate = format!("{key}_{i}");
+                    if !self.offsets.contains_key(candidate.as_str()) {
+                        break candidate;
+                    }
+                    i += 1;
+                };
+                self.offsets.insert(unique.into_boxed_str(), offset);
+            }
+        }
+        Some(prev)
+    }
+
+    /// Check whether any duplicate IDs were encountered while building the index,
+    /// regardless of how they were resolved.
+    pub fn validate(&self) -> Result<(), DuplicateIdError> {
+        match self.duplicates.first() {
+            Some((id, offset)) => Err(DuplicateIdError {
+                name: self.name.clone(),
+                id: id.to_string(),
+                offset: *offset,
+                count: self.duplicates.len(),
+            }),
+            None => Ok(()),
+        }
     }
 
     #[inline]
@@ -99,3 +180,58 @@ impl OffsetIndex {
         serde_json::from_reader(reader)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build(policy: DuplicateIdPolicy) -> OffsetIndex {
+        let mut index = OffsetIndex::new("spectrum".into());
+        index.duplicate_id_policy = policy;
+        index.insert("a", 0);
+        index.insert("b", 10);
+        index.insert("a", 20);
+        index.insert("c", 30);
+        index
+    }
+
+    #[test]
+    fn test_duplicate_policies() {
+        let index = build(DuplicateIdPolicy::KeepFirst);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("a"), Some(0));
+        assert_eq!(index.index_of("a"), Some(0));
+
+        let index = build(DuplicateIdPolicy::Error);
+        assert_eq!(index.get("a"), Some(0));
+        let err = index.validate().unwrap_err();
+        assert_eq!(err.id, "a");
+        assert_eq!(err.offset, 20);
+        assert_eq!(err.count, 1);
+
+        let index = build(DuplicateIdPolicy::KeepLast);
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("a"), Some(20));
+        assert_eq!(index.index_of("a"), Some(0));
+        assert_eq!(index.index_of("c"), Some(2));
+        assert!(index.validate().is_err());
+
+        let mut buf = Vec::new();
+        index.to_writer(&mut buf).unwrap();
+        let dup = OffsetIndex::from_reader(buf.as_slice()).unwrap();
+        assert!(dup.duplicates.is_empty());
+        assert_eq!(dup.get("a"), Some(20));
+
+        let mut index = build(DuplicateIdPolicy::MakeUnique);
+        index.insert("a", 40);
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.get("a"), Some(0));
+        assert_eq!(index.get("a_1"), Some(20));
+        assert_eq!(index.get("a_2"), Some(40));
+        assert_eq!(index.index_of("a_1"), Some(2));
+        assert_eq!(index.duplicates.len(), 2);
+
+        let index = OffsetIndex::new("spectrum".into());
+        assert!(index.validate().is_ok());
+    }
+}
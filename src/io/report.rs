@@ -0,0 +1,311 @@
+//! A one-call summary of the contents of a mass spectrometry data file.
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use crate::curie;
+use crate::io::{infer_from_path, DetailLevel, MZReader, MassSpectrometryFormat};
+use crate::meta::MSDataFileMetadata;
+use crate::params::{ControlledVocabulary, ParamDescribed, ParamLike, ParamValue};
+use crate::prelude::*;
+use crate::spectrum::{IsolationWindow, IsolationWindowState, Precursor, ScanPolarity};
+
+/// Get the bounds of an isolation window in hundredths of an m/z, so that the same window
+/// read in different cycles compares equal.
+fn isolation_window_key(window: &IsolationWindow) -> Option<(i64, i64)> {
+    let (lower, upper) = match window.flags {
+        IsolationWindowState::Offset => (
+            window.target - window.lower_bound,
+            window.target + window.upper_bound,
+        ),
+        IsolationWindowState::Explicit | IsolationWindowState::Complete => {
+            (window.lower_bound, window.upper_bound)
+        }
+        IsolationWindowState::Unknown => return None,
+    };
+    if upper <= lower {
+        return None;
+    }
+    Some((
+        (lower as f64 * 100.0).round() as i64,
+        (upper as f64 * 100.0).round() as i64,
+    ))
+}
+
+/// Whether a precursor describes an ion that was actually observed, with a charge state or
+/// an intensity, rather than just standing in for the center of its isolation window.
+fn has_observed_ion(precursor: &Precursor) -> bool {
+    precursor
+        .ions
+        .iter()
+        .any(|ion| ion.charge.is_some() || ion.intensity > 0.0)
+}
+
+/// The way precursor ions were selected for fragmentation, as inferred from the
+/// MSn spectra in a file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcquisitionStyle {
+    /// There were no spectra, or the MSn spectra did not describe their precursors
+    #[default]
+    Unknown,
+    /// There were no MSn spectra
+    MS1Only,
+    /// Most MSn spectra isolated an observed precursor ion, with a charge state or intensity
+    DataDependent,
+    /// Most MSn spectra isolated one of a fixed set of windows repeated every cycle, without
+    /// selecting an observed precursor ion
+    DataIndependent,
+}
+
+/// A summary of the contents of a mass spectrometry data file, built from spectrum
+/// metadata alone.
+///
+/// See [`quick_report`] to build one from a path, or [`FileReport::from_source`] to
+/// summarize an already opened reader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileReport {
+    /// The file format, if known
+    pub format: MassSpectrometryFormat,
+    /// The total number of spectra read
+    pub spectrum_count: usize,
+    /// The number of spectra at each MS level
+    pub level_counts: HashMap<u8, usize>,
+    /// The distinct scan polarities observed, in order of first appearance
+    pub polarities: Vec<ScanPolarity>,
+    /// The m/z range covered by the spectra, from the observed m/z range when reported
+    /// or the scan windows otherwise
+    pub mz_range: Option<(f64, f64)>,
+    /// The range of scan start times, in minutes
+    pub time_range: Option<(f64, f64)>,
+    /// The instrument model names found in the instrument configurations
+    pub instrument_models: Vec<String>,
+    /// How precursor ions were selected for fragmentation
+    pub acquisition_style: AcquisitionStyle,
+    /// Whether any spectrum carried an ion mobility measure
+    pub has_ion_mobility: bool,
+}
+
+impl Default for FileReport {
+    fn default() -> Self {
+        Self {
+            format: MassSpectrometryFormat::Unknown,
+            spectrum_count: 0,
+            level_counts: HashMap::default(),
+            polarities: Vec::new(),
+            mz_range: None,
+            time_range: None,
+            instrument_models: Vec::new(),
+            acquisition_style: AcquisitionStyle::Unknown,
+            has_ion_mobility: false,
+        }
+    }
+}
+
+fn extend_range(range: &mut Option<(f64, f64)>, low: f64, high: f64) {
+    *range = match range {
+        Some((lo, hi)) => Some((lo.min(low), hi.max(high))),
+        None => Some((low, high)),
+    };
+}
+
+impl FileReport {
+    /// Summarize every spectrum remaining in `reader`.
+    ///
+    /// The reader's [`DetailLevel`] is left as-is, so set it to [`DetailLevel::MetadataOnly`]
+    /// beforehand to avoid decoding peak data.
+    pub fn from_source<
+        C: CentroidLike + Default,
+        D: DeconvolutedCentroidLike + Default,
+        S: SpectrumLike<C, D>,
+        R: SpectrumSource<C, D, S> + MSDataFileMetadata,
+    >(
+        reader: &mut R,
+    ) -> Self {
+        let mut report = Self::default();
+
+        for config in reader.instrument_configurations().values() {
+            for param in config.params() {
+                let name = if param.accession() == Some(1000031) {
+                    param.value().to_string()
+                } else if param.controlled_vocabulary() == Some(ControlledVocabulary::MS)
+                    && param.value().is_empty()
+                {
+                    param.name().to_string()
+                } else {
+                    continue;
+                };
+                if !name.is_empty() && !report.instrument_models.contains(&name) {
+                    report.instrument_models.push(name);
+                }
+            }
+        }
+
+        let lowest_mz = curie!(MS:1000528);
+        let highest_mz = curie!(MS:1000527);
+        let mut msn_count = 0usize;
+        // The isolation window of each MSn spectrum and whether it selected an observed ion
+        let mut msn_precursors: Vec<(Option<(i64, i64)>, bool)> = Vec::new();
+        let mut window_counts: HashMap<(i64, i64), usize> = HashMap::new();
+
+        for spectrum in reader.by_ref() {
+            report.spectrum_count += 1;
+            let level = spectrum.ms_level();
+            *report.level_counts.entry(level).or_default() += 1;
+
+            let polarity = spectrum.polarity();
+            if !report.polarities.contains(&polarity) {
+                report.polarities.push(polarity);
+            }
+
+            let time = spectrum.start_time();
+            extend_range(&mut report.time_range, time, time);
+
+            let descr = spectrum.description();
+            match (
+                descr
+                    .get_param_by_curie(&lowest_mz)
+                    .and_then(|p| p.to_f64().ok()),
+                descr
+                    .get_param_by_curie(&highest_mz)
+                    .and_then(|p| p.to_f64().ok()),
+            ) {
+                (Some(low), Some(high)) => extend_range(&mut report.mz_range, low, high),
+                _ => {
+                    for window in spectrum
+                        .acquisition()
+                        .iter()
+                        .flat_map(|s| s.scan_windows.iter())
+                    {
+                        extend_range(
+                            &mut report.mz_range,
+                            window.lower_bound as f64,
+                            window.upper_bound as f64,
+                        );
+                    }
+                }
+            }
+
+            report.has_ion_mobility |= spectrum.has_ion_mobility();
+
+            if level > 1 {
+                msn_count += 1;
+                if let Some(precursor) = spectrum.precursor() {
+                    let key = isolation_window_key(&precursor.isolation_window);
+                    if let Some(key) = key {
+                        *window_counts.entry(key).or_default() += 1;
+                    }
+                    msn_precursors.push((key, has_observed_ion(precursor)));
+                }
+            }
+        }
+
+        // A data independent scheme cycles through the same windows, with any selected ion
+        // just marking the window center, while a data dependent scheme picks observed ions.
+        let mut independent = 0usize;
+        let mut dependent = 0usize;
+        for (key, observed) in msn_precursors {
+            let repeated = key.is_some_and(|k| window_counts[&k] > 1);
+            if observed {
+                dependent += 1;
+            } else if repeated {
+                independent += 1;
+            }
+        }
+
+        report.acquisition_style = if report.spectrum_count == 0 {
+            AcquisitionStyle::Unknown
+        } else if msn_count == 0 {
+            AcquisitionStyle::MS1Only
+        } else if independent == 0 && dependent == 0 {
+            AcquisitionStyle::Unknown
+        } else if independent > dependent {
+            AcquisitionStyle::DataIndependent
+        } else {
+            AcquisitionStyle::DataDependent
+        };
+
+        report
+    }
+}
+
+/// Open the file at `path` with [`MZReader`] and summarize it with
+/// [`FileReport::from_source`], reading spectra with [`DetailLevel::MetadataOnly`].
+///
+/// # Errors
+/// This returns an error if the file's format cannot be read.
+pub fn quick_report<P: AsRef<Path>>(path: P) -> io::Result<FileReport> {
+    let path = path.as_ref();
+    let (format, _) = infer_from_path(path);
+    let mut reader = MZReader::open_path(path)?;
+    reader.set_detail_level(DetailLevel::MetadataOnly);
+    let mut report = FileReport::from_source(&mut reader);
+    report.format = format;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quick_report() -> io::Result<()> {
+        let report = quick_report("./test/data/small.mzML")?;
+        assert_eq!(report.format, MassSpectrometryFormat::MzML);
+        assert_eq!(report.spectrum_count, 48);
+        assert_eq!(report.level_counts.values().sum::<usize>(), 48);
+        assert_eq!(report.level_counts[&1], 14);
+        assert_eq!(report.polarities, vec![ScanPolarity::Positive]);
+        assert_eq!(report.instrument_models, vec!["LTQ FT".to_string()]);
+        assert_eq!(report.acquisition_style, AcquisitionStyle::DataDependent);
+        assert!(!report.has_ion_mobility);
+
+        let (start, end) = report.time_range.unwrap();
+        assert!(start < end);
+        let (low, high) = report.mz_range.unwrap();
+        assert!(low < high);
+
+        let report = quick_report("./test/data/small.mgf")?;
+        assert_eq!(report.format, MassSpectrometryFormat::MGF);
+        assert_eq!(report.level_counts[&2], 35);
+        assert!(report.instrument_models.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_dia() -> io::Result<()> {
+        use crate::io::{MzMLReader, MzMLWriter};
+
+        // Rewrite the MSn spectra to cycle through ten fixed 4 m/z wide windows, placing an
+        // unobserved ion at the center of each as DIA converters do
+        let mut reader = MzMLReader::open_path("./test/data/small.mzML")?;
+        let mut writer = MzMLWriter::new(io::Cursor::new(Vec::new()));
+        writer.copy_metadata_from(&reader);
+        let mut k = 0;
+        for mut spectrum in reader.iter() {
+            if let Some(precursor) = spectrum.precursor_mut() {
+                let lower = 400.0 + 4.0 * (k % 10) as f32;
+                precursor.isolation_window = IsolationWindow::new(
+                    lower + 2.0,
+                    lower,
+                    lower + 4.0,
+                    IsolationWindowState::Complete,
+                );
+                let ion = precursor.ion_mut();
+                ion.mz = lower as f64 + 2.0;
+                ion.charge = None;
+                ion.intensity = 0.0;
+                k += 1;
+            }
+            writer.write_owned(spectrum)?;
+        }
+        writer.close()?;
+        let buffer = writer.get_mut()?.get_ref().clone();
+
+        let mut reader = MzMLReader::new(io::Cursor::new(buffer));
+        let report = FileReport::from_source(&mut reader);
+        assert_eq!(report.spectrum_count, 48);
+        assert_eq!(report.acquisition_style, AcquisitionStyle::DataIndependent);
+        Ok(())
+    }
+}
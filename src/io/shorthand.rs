@@ -39,7 +39,7 @@ macro_rules! mz_read {
                     $crate::io::MassSpectrometryFormat::MGF => {
                         let handle = std::fs::File::open(read_path)?;
                         if is_gzipped {
-                            let fh = $crate::io::RestartableGzDecoder::new(std::io::BufReader::new(handle));
+                            let fh = $crate::io::RestartableDecoder::detect(std::io::BufReader::new(handle))?;
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new($crate::io::mgf::MGFReaderType::<_, $C, $D>::new(fh));
                             Ok($impl)
@@ -53,7 +53,7 @@ macro_rules! mz_read {
                         let handle = std::fs::File::open(read_path)?;
 
                         if is_gzipped {
-                            let fh = $crate::io::RestartableGzDecoder::new(std::io::BufReader::new(handle));
+                            let fh = $crate::io::RestartableDecoder::detect(std::io::BufReader::new(handle))?;
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new($crate::io::mzml::MzMLReaderType::<_, $C, $D>::new(fh));
                             Ok($impl)
@@ -97,7 +97,7 @@ macro_rules! mz_read {
                         let handle = std::io::BufReader::new(handle);
                         #[allow(unused_mut)]
                         if is_gzipped {
-                            let fh = $crate::io::RestartableGzDecoder::new(std::io::BufReader::new(handle));
+                            let fh = $crate::io::RestartableDecoder::detect(std::io::BufReader::new(handle))?;
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new($crate::io::mgf::MGFReaderType::<_, $C, $D>::new(fh));
                             Ok($impl)
@@ -111,7 +111,7 @@ macro_rules! mz_read {
                         let handle = std::io::BufReader::new(handle);
                         #[allow(unused_mut)]
                         if is_gzipped {
-                            let fh = $crate::io::RestartableGzDecoder::new(std::io::BufReader::new(handle));
+                            let fh = $crate::io::RestartableDecoder::detect(std::io::BufReader::new(handle))?;
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new($crate::io::mzml::MzMLReaderType::<_, $C, $D>::new(fh));
                             Ok($impl)
@@ -145,7 +145,7 @@ macro_rules! mz_read {
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new(
                                 $crate::io::mgf::MGFReaderType::new(
-                                    $crate::io::RestartableGzDecoder::new(std::io::BufReader::new(buffered)),
+                                    $crate::io::RestartableDecoder::detect(std::io::BufReader::new(buffered))?,
                             ));
                             Ok($impl)
                         } else {
@@ -159,7 +159,7 @@ macro_rules! mz_read {
                         if compressed {
                             #[allow(unused_mut)]
                             let mut $reader: $crate::io::StreamingSpectrumIterator<$C, $D, _, _> = $crate::io::StreamingSpectrumIterator::new(
-                                $crate::io::mzml::MzMLReaderType::new($crate::io::RestartableGzDecoder::new(std::io::BufReader::new(buffered)),
+                                $crate::io::mzml::MzMLReaderType::new($crate::io::RestartableDecoder::detect(std::io::BufReader::new(buffered))?,
                             ));
                             Ok($impl)
                         } else {
@@ -1,6 +1,7 @@
 use crate::{
     meta::{Component, ComponentType, InletTypeTerm, IonizationTypeTerm},
-    params::ParamDescribed,
+    params::{ParamDescribed, Unit},
+    spectrum::ChromatogramType,
 };
 
 #[allow(non_camel_case_types)]
@@ -127,3 +128,113 @@ impl From<u8> for MsMsType {
         }
     }
 }
+
+/// The kind of signal recorded by a trace in `chromatography-data.sqlite`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceType {
+    NoneTrace = 0,
+    ChromMS = 1,
+    ChromMSMS = 2,
+    ChromUV = 3,
+    ChromPressure = 4,
+    ChromSolventMix = 5,
+    ChromFlow = 6,
+    ChromTemperature = 7,
+    ChromUserDefined = 8,
+    #[default]
+    Unknown = -1,
+}
+
+impl TraceType {
+    pub const fn chromatogram_type(&self) -> ChromatogramType {
+        match self {
+            Self::ChromPressure => ChromatogramType::PressureChromatogram,
+            Self::ChromFlow => ChromatogramType::FlowRateChromatogram,
+            Self::ChromUV => ChromatogramType::AbsorptionChromatogram,
+            _ => ChromatogramType::Unknown,
+        }
+    }
+}
+
+impl From<i64> for TraceType {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::NoneTrace,
+            1 => Self::ChromMS,
+            2 => Self::ChromMSMS,
+            3 => Self::ChromUV,
+            4 => Self::ChromPressure,
+            5 => Self::ChromSolventMix,
+            6 => Self::ChromFlow,
+            7 => Self::ChromTemperature,
+            8 => Self::ChromUserDefined,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// The unit of the values recorded by a trace in `chromatography-data.sqlite`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceUnit {
+    NoneUnit = 0,
+    LengthNanometer = 1,
+    FlowMicroliterPerMinute = 2,
+    PressureBar = 3,
+    Percent = 4,
+    TemperatureDegreeCelsius = 5,
+    Intensity = 6,
+    #[default]
+    Unknown = -1,
+}
+
+impl TraceUnit {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::NoneUnit => "",
+            Self::LengthNanometer => "nanometer",
+            Self::FlowMicroliterPerMinute => "microliters per minute",
+            Self::PressureBar => "bar",
+            Self::Percent => "percent",
+            Self::TemperatureDegreeCelsius => "degree Celsius",
+            Self::Intensity => "intensity",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// The [`Unit`] the trace's values are reported in once scaled by [`TraceUnit::scale`].
+    ///
+    /// The unit ontology has no term for bar, so pressures are reported in pascal.
+    pub const fn unit(&self) -> Unit {
+        match self {
+            Self::LengthNanometer => Unit::Nanometer,
+            Self::FlowMicroliterPerMinute => Unit::MicrolitersPerMinute,
+            Self::PressureBar => Unit::Pascal,
+            Self::Percent => Unit::PercentElectronVolt,
+            Self::TemperatureDegreeCelsius => Unit::DegreeCelsius,
+            Self::NoneUnit | Self::Intensity | Self::Unknown => Unit::Unknown,
+        }
+    }
+
+    /// The factor to multiply the stored values by to express them in [`TraceUnit::unit`]
+    pub const fn scale(&self) -> f32 {
+        match self {
+            Self::PressureBar => 1e5,
+            _ => 1.0,
+        }
+    }
+}
+
+impl From<i64> for TraceUnit {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::NoneUnit,
+            1 => Self::LengthNanometer,
+            2 => Self::FlowMicroliterPerMinute,
+            3 => Self::PressureBar,
+            4 => Self::Percent,
+            5 => Self::TemperatureDegreeCelsius,
+            6 => Self::Intensity,
+            _ => Self::Unknown,
+        }
+    }
+}
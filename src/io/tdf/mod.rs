@@ -1,6 +1,7 @@
 mod constants;
 mod arrays;
 mod sql;
+mod traces;
 mod reader;
 
 pub use reader::{TDFFrameReader, TDFFrameReaderType, TDFSpectrumReader, TDFSpectrumReaderType, is_tdf};
\ No newline at end of file
@@ -47,9 +47,10 @@ use super::arrays::consolidate_peaks;
 pub use super::arrays::FrameToArraysMapper;
 use super::constants::{InstrumentSource, MsMsType};
 use super::sql::{
-    FromSQL, PasefPrecursor, RawTDFSQLReader,
-    SQLDIAFrameMsMsWindow, SQLFrame, SQLPasefFrameMsMs, SQLPrecursor, TDFMSnFacet,
+    FromSQL, PasefPrecursor, RawTDFSQLReader, SQLDIAFrameMsMsWindow, SQLFrame, SQLPasefFrameMsMs,
+    SQLPrecursor, TDFMSnFacet,
 };
+use super::traces::InstrumentTraces;
 
 const PEAK_MERGE_TOLERANCE: Tolerance = Tolerance::Da(0.01);
 
@@ -142,6 +143,7 @@ pub struct TDFFrameReaderType<
     metadata: timsrust::Metadata,
     frame_reader: timsrust::readers::FrameReader,
     tdf_reader: RawTDFSQLReader,
+    traces: Option<InstrumentTraces>,
     entry_index: Vec<IndexExtry>,
     index: usize,
     offset_index: OffsetIndex,
@@ -200,11 +202,13 @@ impl<C: FeatureLike<MZ, IonMobility>, D: FeatureLike<Mass, IonMobility> + KnownC
         let frame_reader = FrameReader::new(&path)?;
         let tdf_reader = RawTDFSQLReader::new(&tdf_path)
             .map_err(|e| TimsRustError::FrameReaderError(FrameReaderError::SqlError(e.into())))?;
+        let traces = InstrumentTraces::open(path);
 
         let mut this = Self {
             metadata,
             frame_reader,
             tdf_reader,
+            traces,
             entry_index: Vec::new(),
             index: 0,
             offset_index: OffsetIndex::new("spectrum".into()),
@@ -464,6 +468,20 @@ impl<C: FeatureLike<MZ, IonMobility>, D: FeatureLike<Mass, IonMobility> + KnownC
         Some(Chromatogram::new(descr, arrays))
     }
 
+    /// List the instrument traces (e.g. pump pressure, column temperature) stored alongside
+    /// the run, in the order they are served by [`ChromatogramSource`] after the TIC and BPC.
+    ///
+    /// The trace data themselves are only read when requested through
+    /// [`ChromatogramSource::get_chromatogram_by_id`] or
+    /// [`ChromatogramSource::get_chromatogram_by_index`], using the `id` of the returned
+    /// descriptions, which take the form `trace=<Id>`.
+    pub fn list_traces(&self) -> Vec<ChromatogramDescription> {
+        self.traces
+            .as_ref()
+            .map(|traces| traces.descriptions())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn tic(&self) -> Chromatogram {
         self.build_chromatogram(ChromatogramType::TotalIonCurrentChromatogram)
             .unwrap()
@@ -838,7 +856,7 @@ impl<C: FeatureLike<MZ, IonMobility>, D: FeatureLike<Mass, IonMobility> + KnownC
         match id {
             "TIC" => Some(self.tic()),
             "BPC" => Some(self.bpc()),
-            _ => None,
+            _ => self.traces.as_ref()?.get_by_id(id),
         }
     }
 
@@ -846,7 +864,7 @@ impl<C: FeatureLike<MZ, IonMobility>, D: FeatureLike<Mass, IonMobility> + KnownC
         match index {
             0 => Some(self.tic()),
             1 => Some(self.bpc()),
-            _ => None,
+            _ => self.traces.as_ref()?.get_by_index(index),
         }
     }
 }
@@ -1185,6 +1203,14 @@ impl<
     pub fn peak_merging_tolerance_mut(&mut self) -> &mut Tolerance {
         &mut self.peak_merging_tolerance
     }
+
+    /// List the instrument traces stored alongside the run.
+    ///
+    /// # See also
+    /// [`TDFFrameReaderType::list_traces`]
+    pub fn list_traces(&self) -> Vec<ChromatogramDescription> {
+        self.frame_reader.list_traces()
+    }
 }
 
 pub type TDFSpectrumReader = TDFSpectrumReaderType<
@@ -1213,7 +1239,7 @@ fn index_to_precursor(
         let mut act = Activation::default();
         let mut isolation = IsolationWindow::default();
         if let Some(pasef) = index_entry.pasef_msms() {
-            act.energy = pasef.collision_energy as f32;
+            act.set_energy_with_unit(pasef.collision_energy as f32, Unit::Electronvolt);
             act.methods_mut().push(CollisionInducedDissociation);
 
             let iso_width = pasef.isolation_width / 2.0;
@@ -1223,7 +1249,7 @@ fn index_to_precursor(
             isolation.flags = IsolationWindowState::Complete;
         }
         if let Some(pasef) = index_entry.dia_window() {
-            act.energy = pasef.collision_energy as f32;
+            act.set_energy_with_unit(pasef.collision_energy as f32, Unit::Electronvolt);
             act.methods_mut().push(CollisionInducedDissociation);
 
             let iso_width = pasef.isolation_width / 2.0;
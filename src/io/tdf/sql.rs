@@ -1,10 +1,12 @@
-use std::{collections::HashMap, path::Path, sync::Arc};
+use std::{collections::HashMap, convert::TryInto, path::Path, sync::Arc};
 
 use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
 use rusqlite::{Connection, Error, Params, Row};
 
 use crate::{params::Value, spectrum::ScanPolarity};
 
+use super::constants::{TraceType, TraceUnit};
+
 pub trait FromSQL: Sized {
     fn from_row(row: &Row<'_>) -> Result<Self, Error>;
 
@@ -257,6 +259,66 @@ impl FromSQL for SQLFrame {
     }
 }
 
+/// An instrument trace (e.g. pump pressure, column temperature) described in the
+/// `TraceSources` table of `chromatography-data.sqlite`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SQLTraceSource {
+    pub id: usize,
+    pub description: String,
+    pub instrument: String,
+    pub trace_type: TraceType,
+    pub unit: TraceUnit,
+    pub time_offset: f64,
+}
+
+impl FromSQL for SQLTraceSource {
+    fn from_row(row: &Row<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            id: row.get(0)?,
+            description: row.get::<usize, Option<String>>(1)?.unwrap_or_default(),
+            instrument: row.get::<usize, Option<String>>(2)?.unwrap_or_default(),
+            trace_type: TraceType::from(row.get::<usize, i64>(3)?),
+            unit: TraceUnit::from(row.get::<usize, i64>(4)?),
+            time_offset: row.get::<usize, Option<f64>>(5)?.unwrap_or_default(),
+        })
+    }
+
+    fn get_sql() -> String {
+        "SELECT Id, Description, Instrument, Type, Unit, TimeOffset FROM TraceSources".into()
+    }
+}
+
+/// A block of time points (in seconds) and values for a [`SQLTraceSource`], stored
+/// in the `TraceChunks` table of `chromatography-data.sqlite`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SQLTraceChunk {
+    pub trace: usize,
+    pub times: Vec<f64>,
+    pub values: Vec<f32>,
+}
+
+impl FromSQL for SQLTraceChunk {
+    fn from_row(row: &Row<'_>) -> Result<Self, Error> {
+        let times: Vec<u8> = row.get(1)?;
+        let values: Vec<u8> = row.get(2)?;
+        Ok(Self {
+            trace: row.get(0)?,
+            times: times
+                .chunks_exact(8)
+                .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+                .collect(),
+            values: values
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+                .collect(),
+        })
+    }
+
+    fn get_sql() -> String {
+        "SELECT Trace, Times, Intensities FROM TraceChunks".into()
+    }
+}
+
 #[derive(Debug)]
 pub struct RawTDFSQLReader {
     pub connection: ReentrantMutex<Connection>,
@@ -360,6 +422,14 @@ impl<'a> RawTDFSQLReader {
         }
     }
 
+    pub fn trace_sources(&self) -> Result<Vec<SQLTraceSource>, Error> {
+        SQLTraceSource::read_from(&self.connection(), [])
+    }
+
+    pub fn trace_chunks_for(&self, trace_id: usize) -> Result<Vec<SQLTraceChunk>, Error> {
+        SQLTraceChunk::read_from_where(&self.connection(), [trace_id], "Trace = ? ORDER BY Id")
+    }
+
     pub fn dia_window_group_for(&self, frame_id: usize) -> Result<u32, Error> {
         let conn = self.connection();
         let mut stmt = conn.prepare("SELECT WindowGroup FROM DiaFrameMsMsInfo WHERE Frame = ?")?;
@@ -0,0 +1,244 @@
+use std::path::Path;
+
+use rusqlite::Error;
+
+use crate::{
+    params::{Param, ParamDescribed, Unit},
+    spectrum::{
+        ArrayType, BinaryArrayMap, BinaryDataArrayType, Chromatogram, ChromatogramDescription,
+        DataArray,
+    },
+};
+
+use super::sql::{RawTDFSQLReader, SQLTraceSource};
+
+/// Instrument traces are indexed after the TIC and BPC chromatograms
+pub(crate) const TRACE_INDEX_OFFSET: usize = 2;
+
+/// The instrument traces (e.g. pump pressure, column temperature) stored in the
+/// `chromatography-data.sqlite` file alongside a run.
+#[derive(Debug)]
+pub(crate) struct InstrumentTraces {
+    reader: RawTDFSQLReader,
+    traces: Vec<SQLTraceSource>,
+}
+
+impl InstrumentTraces {
+    pub fn from_reader(reader: RawTDFSQLReader) -> Result<Self, Error> {
+        let traces = reader.trace_sources()?;
+        Ok(Self { reader, traces })
+    }
+
+    /// Open the optional `chromatography-data.sqlite` file in the `.d` directory at `path`.
+    /// Traces are auxiliary data, so a missing or unreadable file is not an error.
+    pub fn open(path: &Path) -> Option<Self> {
+        let trace_path = path.join("chromatography-data.sqlite");
+        if !trace_path.exists() {
+            return None;
+        }
+        match RawTDFSQLReader::new(&trace_path).and_then(Self::from_reader) {
+            Ok(this) => Some(this),
+            Err(e) => {
+                log::warn!("Failed to read traces from {}: {e}", trace_path.display());
+                None
+            }
+        }
+    }
+
+    fn description(&self, i: usize, trace: &SQLTraceSource) -> ChromatogramDescription {
+        let mut descr = ChromatogramDescription {
+            id: format!("trace={}", trace.id),
+            index: TRACE_INDEX_OFFSET + i,
+            chromatogram_type: trace.trace_type.chromatogram_type(),
+            ..Default::default()
+        };
+        if !trace.description.is_empty() {
+            descr.add_param(Param::new_key_value(
+                "trace description",
+                trace.description.clone(),
+            ));
+        }
+        if !trace.instrument.is_empty() {
+            descr.add_param(Param::new_key_value(
+                "trace instrument",
+                trace.instrument.clone(),
+            ));
+        }
+        // Units without a controlled vocabulary term can't be carried by the value array
+        if trace.unit.unit() == Unit::Unknown && !trace.unit.name().is_empty() {
+            descr.add_param(Param::new_key_value("trace unit", trace.unit.name()));
+        }
+        descr
+    }
+
+    pub fn descriptions(&self) -> Vec<ChromatogramDescription> {
+        self.traces
+            .iter()
+            .enumerate()
+            .map(|(i, trace)| self.description(i, trace))
+            .collect()
+    }
+
+    fn build(&self, i: usize) -> Option<Chromatogram> {
+        let trace = self.traces.get(i)?;
+        let chunks = match self.reader.trace_chunks_for(trace.id) {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                log::error!("Failed to read trace {}: {e}", trace.id);
+                return None;
+            }
+        };
+
+        let scale = trace.unit.scale();
+        let n: usize = chunks.iter().map(|c| c.times.len()).sum();
+        let mut time_array: Vec<u8> =
+            Vec::with_capacity(n * BinaryDataArrayType::Float64.size_of());
+        let mut value_array: Vec<u8> =
+            Vec::with_capacity(n * BinaryDataArrayType::Float32.size_of());
+        for chunk in chunks.iter() {
+            for (t, v) in chunk.times.iter().zip(chunk.values.iter()) {
+                time_array.extend_from_slice(&((t + trace.time_offset) / 60.0).to_le_bytes());
+                value_array.extend_from_slice(&(v * scale).to_le_bytes());
+            }
+        }
+
+        let mut arrays = BinaryArrayMap::default();
+        let mut time_array = DataArray::wrap(
+            &ArrayType::TimeArray,
+            BinaryDataArrayType::Float64,
+            time_array,
+        );
+        time_array.unit = Unit::Minute;
+        arrays.add(time_array);
+
+        let mut value_array = DataArray::wrap(
+            &ArrayType::IntensityArray,
+            BinaryDataArrayType::Float32,
+            value_array,
+        );
+        value_array.unit = trace.unit.unit();
+        arrays.add(value_array);
+
+        Some(Chromatogram::new(self.description(i, trace), arrays))
+    }
+
+    /// Read the trace whose chromatogram ID is `trace=<Id>`
+    pub fn get_by_id(&self, id: &str) -> Option<Chromatogram> {
+        let trace_id: usize = id.strip_prefix("trace=")?.parse().ok()?;
+        let i = self.traces.iter().position(|t| t.id == trace_id)?;
+        self.build(i)
+    }
+
+    /// Read the trace at chromatogram `index`, counting the TIC and BPC that precede it
+    pub fn get_by_index(&self, index: usize) -> Option<Chromatogram> {
+        self.build(index.checked_sub(TRACE_INDEX_OFFSET)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::spectrum::{bindata::ByteArrayView, ChromatogramLike, ChromatogramType};
+    use parking_lot::ReentrantMutex;
+    use rusqlite::{params, Connection};
+
+    fn to_bytes_f64(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn to_bytes_f32(values: &[f32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn make_traces() -> Result<InstrumentTraces, Error> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TABLE TraceSources (Id INTEGER PRIMARY KEY, Description TEXT, Instrument TEXT, Type INTEGER, Unit INTEGER, TimeOffset REAL);
+             CREATE TABLE TraceChunks (Id INTEGER PRIMARY KEY, Trace INTEGER, Times BLOB, Intensities BLOB);",
+        )?;
+        conn.execute(
+            "INSERT INTO TraceSources VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![3, "Pump Pressure", "nanoElute", 4, 3, 30.0],
+        )?;
+        conn.execute(
+            "INSERT INTO TraceSources VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![7, "Column Temperature", None::<String>, 7, 5, None::<f64>],
+        )?;
+        conn.execute(
+            "INSERT INTO TraceChunks VALUES (?1, ?2, ?3, ?4)",
+            params![1, 3, to_bytes_f64(&[0.0, 30.0]), to_bytes_f32(&[250.0, 251.5])],
+        )?;
+        conn.execute(
+            "INSERT INTO TraceChunks VALUES (?1, ?2, ?3, ?4)",
+            params![2, 7, to_bytes_f64(&[0.0, 60.0, 120.0]), to_bytes_f32(&[50.0, 50.0, 50.1])],
+        )?;
+        conn.execute(
+            "INSERT INTO TraceChunks VALUES (?1, ?2, ?3, ?4)",
+            params![3, 3, to_bytes_f64(&[90.0]), to_bytes_f32(&[252.0])],
+        )?;
+        InstrumentTraces::from_reader(RawTDFSQLReader {
+            connection: ReentrantMutex::new(conn),
+        })
+    }
+
+    #[test]
+    fn test_trace_sources() -> Result<(), Error> {
+        let traces = make_traces()?;
+        assert_eq!(traces.traces.len(), 2);
+
+        let pressure = &traces.traces[0];
+        assert_eq!(pressure.id, 3);
+        assert_eq!(pressure.description, "Pump Pressure");
+        assert_eq!(pressure.instrument, "nanoElute");
+        assert_eq!(pressure.unit.unit(), Unit::Pascal);
+        assert_eq!(pressure.time_offset, 30.0);
+
+        let temperature = &traces.traces[1];
+        assert_eq!(temperature.id, 7);
+        assert!(temperature.instrument.is_empty());
+        assert_eq!(temperature.unit.unit(), Unit::DegreeCelsius);
+        assert_eq!(temperature.time_offset, 0.0);
+
+        let descriptions = traces.descriptions();
+        assert_eq!(descriptions[0].id, "trace=3");
+        assert_eq!(descriptions[0].index, 2);
+        assert_eq!(
+            descriptions[0].chromatogram_type,
+            ChromatogramType::PressureChromatogram
+        );
+        assert!(descriptions[0].get_param_by_name("trace unit").is_none());
+        assert_eq!(descriptions[1].id, "trace=7");
+        assert_eq!(descriptions[1].index, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_chromatogram() -> Result<(), Error> {
+        let traces = make_traces()?;
+
+        let chrom = traces.get_by_id("trace=3").unwrap();
+        assert_eq!(chrom.id(), "trace=3");
+        let time = chrom.arrays.get(&ArrayType::TimeArray).unwrap();
+        assert_eq!(time.unit, Unit::Minute);
+        assert_eq!(time.to_f64().unwrap().as_ref(), &[0.5, 1.0, 2.0]);
+        let values = chrom.arrays.get(&ArrayType::IntensityArray).unwrap();
+        assert_eq!(values.unit, Unit::Pascal);
+        assert_eq!(
+            values.to_f32().unwrap().as_ref(),
+            &[250.0e5, 251.5e5, 252.0e5]
+        );
+
+        let chrom = traces.get_by_index(3).unwrap();
+        assert_eq!(chrom.id(), "trace=7");
+        let time = chrom.arrays.get(&ArrayType::TimeArray).unwrap();
+        assert_eq!(time.to_f64().unwrap().as_ref(), &[0.0, 1.0, 2.0]);
+        let values = chrom.arrays.get(&ArrayType::IntensityArray).unwrap();
+        assert_eq!(values.unit, Unit::DegreeCelsius);
+
+        assert!(traces.get_by_id("trace=4").is_none());
+        assert!(traces.get_by_id("TIC").is_none());
+        assert!(traces.get_by_index(1).is_none());
+        assert!(traces.get_by_index(4).is_none());
+        Ok(())
+    }
+}
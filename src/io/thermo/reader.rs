@@ -90,6 +90,30 @@ fn make_native_id(index: i32) -> String {
 
 const SOURCE_FILE_ID: &'static str = "RAW1";
 
+/// Linearly interpolate `values` sampled at the sorted positions `positions` at `x`,
+/// clamping to the first and last sample outside of the sampled range.
+#[allow(unused)]
+fn interpolate_sampled(positions: &[f64], values: &[f32], x: f64) -> f32 {
+    let n = positions.len().min(values.len());
+    if n == 0 {
+        return 0.0;
+    }
+    let i = positions[..n].partition_point(|p| *p < x);
+    if i == 0 {
+        values[0]
+    } else if i >= n {
+        values[n - 1]
+    } else {
+        let (x0, x1) = (positions[i - 1], positions[i]);
+        let (y0, y1) = (values[i - 1] as f64, values[i] as f64);
+        if x1 == x0 {
+            y0 as f32
+        } else {
+            (y0 + (y1 - y0) * (x - x0) / (x1 - x0)) as f32
+        }
+    }
+}
+
 #[cfg(not(feature = "doc-only"))]
 pub(crate) mod sealed {
     use super::*;
@@ -120,6 +144,7 @@ pub(crate) mod sealed {
         pub(crate) samples: Vec<Sample>,
         pub(crate) data_processings: Vec<DataProcessing>,
         pub(crate) ms_run: MassSpectrometryRun,
+        pub(crate) load_baseline_noise: bool,
         _c: PhantomData<C>,
         _d: PhantomData<D>,
     }
@@ -174,6 +199,7 @@ pub(crate) mod sealed {
                 softwares: vec![sw],
                 data_processings: vec![],
                 ms_run: ms_run,
+                load_baseline_noise: false,
                 _c: PhantomData,
                 _d: PhantomData,
             })
@@ -203,6 +229,29 @@ pub(crate) mod sealed {
             self.handle.set_centroid_spectra(value)
         }
 
+        /// Get whether or not to load the noise and baseline arrays for centroid spectra
+        pub fn get_load_baseline_noise(&self) -> bool {
+            self.load_baseline_noise
+        }
+
+        /// Set whether or not to load the noise and baseline arrays for centroid spectra.
+        ///
+        /// When enabled, centroid spectra will carry their m/z and intensity arrays in
+        /// [`MultiLayerSpectrum::arrays`] alongside their peaks, plus the following arrays
+        /// derived from the RAW file's noise packets:
+        ///
+        /// | RAW label field | Array |
+        /// |-----------------|-------|
+        /// | Noise           | [`ArrayType::SignalToNoiseArray`], as intensity divided by noise |
+        /// | Baseline        | [`ArrayType::BaselineArray`] |
+        ///
+        /// The noise packets are sampled across the m/z range rather than stored per peak,
+        /// so the value at each centroid is linearly interpolated between the nearest samples.
+        /// This requires an extra read per spectrum.
+        pub fn set_load_baseline_noise(&mut self, value: bool) {
+            self.load_baseline_noise = value
+        }
+
         fn unpack_chromatogram_signal(&self, descr: thermorawfilereader::ChromatogramDescription) -> BinaryArrayMap {
             let mut array_map = BinaryArrayMap::default();
             if let Some(data) = descr.data() {
@@ -618,7 +667,11 @@ pub(crate) mod sealed {
 
             let activation = &mut precursor.activation;
             let vact = vprec.activation();
-            activation.energy = vact.collision_energy() as f32;
+            // Thermo instruments report normalized collision energy
+            activation.set_energy_with_unit(
+                vact.collision_energy() as f32,
+                Unit::PercentElectronVolt,
+            );
             match vact.dissociation_method() {
                 DissociationMethod::CID => {
                     activation
@@ -769,6 +822,53 @@ pub(crate) mod sealed {
             arrays
         }
 
+        fn populate_baseline_noise(
+            &self,
+            index: usize,
+            data: &SpectrumData,
+            arrays: &mut BinaryArrayMap,
+        ) {
+            let (mz, intensity) = match (data.mz(), data.intensity()) {
+                (Some(mz), Some(intensity)) => (mz, intensity),
+                _ => return,
+            };
+            let packet = match self.handle.get_baseline_noise(index) {
+                Some(packet) if packet.check() => packet,
+                _ => return,
+            };
+            let view = packet.view();
+            let (sample_mz, sample_noise, sample_baseline) =
+                match (view.mass(), view.noise(), view.baseline()) {
+                    (Some(m), Some(n), Some(b)) if m.len() > 0 => (
+                        m.iter().collect::<Vec<f64>>(),
+                        n.iter().collect::<Vec<f32>>(),
+                        b.iter().collect::<Vec<f32>>(),
+                    ),
+                    _ => return,
+                };
+
+            let mut snr = Vec::with_capacity(mz.len());
+            let mut baseline = Vec::with_capacity(mz.len());
+            for (mz_i, intensity_i) in mz.iter().zip(intensity) {
+                let noise_i = interpolate_sampled(&sample_mz, &sample_noise, mz_i);
+                snr.push(if noise_i > 0.0 { intensity_i / noise_i } else { 0.0 });
+                baseline.push(interpolate_sampled(&sample_mz, &sample_baseline, mz_i));
+            }
+
+            let mut snr_array = DataArray::from_name_and_type(
+                &ArrayType::SignalToNoiseArray,
+                BinaryDataArrayType::Float32,
+            );
+            snr_array.extend(&snr).unwrap();
+            arrays.add(snr_array);
+
+            let mut baseline_array =
+                DataArray::from_name_and_type(&ArrayType::BaselineArray, BinaryDataArrayType::Float32);
+            baseline_array.unit = Unit::DetectorCounts;
+            baseline_array.extend(&baseline).unwrap();
+            arrays.add(baseline_array);
+        }
+
         fn populate_peaks(&self, data: &SpectrumData) -> PeakSetVec<C, MZ> {
             let mut peaks = PeakSetVec::empty();
             if let (Some(mz), Some(intensity)) = (data.mz(), data.intensity()) {
@@ -825,6 +925,11 @@ pub(crate) mod sealed {
             if let Some(data) = view.data() {
                 if spec.signal_continuity() == SignalContinuity::Centroid {
                     spec.peaks = Some(self.populate_peaks(&data));
+                    if self.load_baseline_noise {
+                        let mut arrays = self.populate_raw_signal(&data);
+                        self.populate_baseline_noise(index, &data, &mut arrays);
+                        spec.arrays = Some(arrays);
+                    }
                 } else {
                     spec.arrays = Some(self.populate_raw_signal(&data));
                 }
@@ -910,6 +1015,7 @@ pub(crate) mod stub {
         pub(crate) samples: Vec<Sample>,
         pub(crate) data_processings: Vec<DataProcessing>,
         pub(crate) ms_run: MassSpectrometryRun,
+        pub(crate) load_baseline_noise: bool,
         _c: PhantomData<C>,
         _d: PhantomData<D>,
     }
@@ -948,6 +1054,7 @@ pub(crate) mod stub {
                 softwares: Vec::new(),
                 data_processings: vec![],
                 ms_run: MassSpectrometryRun::default(),
+                load_baseline_noise: false,
                 _c: PhantomData,
                 _d: PhantomData,
             })
@@ -975,6 +1082,16 @@ pub(crate) mod stub {
         /// Set whether or not to centroid spectra on read using the vendor algorithm
         pub fn set_centroiding(&mut self, value: bool) {}
 
+        /// Get whether or not to load the noise and baseline arrays for centroid spectra
+        pub fn get_load_baseline_noise(&self) -> bool {
+            self.load_baseline_noise
+        }
+
+        /// Set whether or not to load the noise and baseline arrays for centroid spectra
+        pub fn set_load_baseline_noise(&mut self, value: bool) {
+            self.load_baseline_noise = value
+        }
+
         pub fn get_tic(&mut self) -> Chromatogram {
             Chromatogram::default()
         }
@@ -1164,6 +1281,17 @@ mod test {
     use super::*;
     use crate::MzMLReader;
 
+    #[test]
+    fn test_interpolate_sampled() {
+        let positions = [100.0, 200.0, 300.0];
+        let values = [10.0f32, 20.0, 40.0];
+        assert_eq!(interpolate_sampled(&positions, &values, 50.0), 10.0);
+        assert_eq!(interpolate_sampled(&positions, &values, 150.0), 15.0);
+        assert_eq!(interpolate_sampled(&positions, &values, 250.0), 30.0);
+        assert_eq!(interpolate_sampled(&positions, &values, 500.0), 40.0);
+        assert_eq!(interpolate_sampled(&[], &[], 500.0), 0.0);
+    }
+
     #[test]
     fn test_read_metadata() -> io::Result<()> {
         let reader = ThermoRawReader::open_path("./test/data/small.RAW")?;
@@ -1240,6 +1368,10 @@ mod test {
                     ps.ion().mz() - pr.ion().mz()
                 );
                 assert_eq!(ps.ion().charge, pr.ion().charge);
+                assert_eq!(
+                    ps.activation.energy_with_unit(),
+                    (ps.activation.energy, Unit::PercentElectronVolt)
+                );
             }
         });
 
@@ -1314,6 +1446,20 @@ mod test {
         let spec_centr = reader.get_spectrum_by_index(spec.index()).unwrap();
         assert_eq!(spec_centr.signal_continuity(), SignalContinuity::Centroid);
         assert!(spec_centr.peaks.is_some());
+        assert!(spec_centr.arrays.is_none());
+
+        assert!(!reader.get_load_baseline_noise());
+        reader.set_load_baseline_noise(true);
+        let spec_centr = reader.get_spectrum_by_index(spec.index()).unwrap();
+        let arrays = spec_centr.arrays.as_ref().unwrap();
+        let n_peaks = spec_centr.peaks.as_ref().unwrap().len();
+        assert_eq!(arrays.mzs()?.len(), n_peaks);
+        // The first spectrum is an Orbitrap MS1 scan, which always carries a noise packet
+        let snr = arrays.get(&ArrayType::SignalToNoiseArray).unwrap();
+        assert_eq!(snr.to_f32()?.len(), n_peaks);
+        let baseline = arrays.get(&ArrayType::BaselineArray).unwrap();
+        assert_eq!(baseline.to_f32()?.len(), n_peaks);
+        reader.set_load_baseline_noise(false);
 
         let scan = reader.start_from_index(20).unwrap().next().unwrap();
         assert_eq!(scan.index(), 20);
@@ -4,9 +4,9 @@ mod spectrum;
 mod util;
 
 pub use spectrum::{
-    MZFileReader, MemorySpectrumSource, RandomAccessSpectrumGroupingIterator,
-    RandomAccessSpectrumIterator, RandomAccessSpectrumSource, SpectrumAccessError,
-    SpectrumIterator, SpectrumReceiver, SpectrumSource,
+    DynSpectrumSource, MZFileReader, MemorySpectrumSource, RandomAccessSpectrumGroupingIterator,
+    RandomAccessSpectrumIterator, RandomAccessSpectrumSource, ReverseTimeSpectrumIterator,
+    SpectrumAccessError, SpectrumIterator, SpectrumReceiver, SpectrumSource,
     SpectrumSourceWithMetadata, SpectrumWriter, StreamingSpectrumIterator,
 };
 pub use util::SeekRead;
@@ -36,4 +36,54 @@ mod test {
         // couldn't compile.
         let _f = |_x: &dyn SpectrumSource| {};
     }
+
+    #[test]
+    fn test_dyn_spectrum_source() -> std::io::Result<()> {
+        use crate::spectrum::SpectrumLike;
+
+        let mut readers: Vec<Box<dyn DynSpectrumSource>> = vec![
+            Box::new(crate::MzMLReader::open_path("./test/data/small.mzML")?),
+            Box::new(crate::MGFReader::open_path("./test/data/small.mgf")?),
+        ];
+        for reader in readers.iter_mut() {
+            assert!(!reader.dyn_is_empty());
+            let n = reader.dyn_len();
+            let scan = reader.dyn_get_spectrum_by_index(0).unwrap();
+            assert_eq!(scan.index(), 0);
+            let dup = reader.dyn_get_spectrum_by_id(scan.id()).unwrap();
+            assert_eq!(dup.index(), 0);
+            reader.dyn_reset();
+            assert_eq!(reader.count(), n);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_rev_time() -> std::io::Result<()> {
+        use crate::spectrum::SpectrumLike;
+
+        let mut reader = crate::MzMLReader::open_path("./test/data/small.mzML")?;
+        let n = reader.len();
+        let times: Vec<f64> = reader.iter_rev_time().map(|s| s.start_time()).collect();
+        assert_eq!(times.len(), n);
+        assert!(times.windows(2).all(|w| w[0] >= w[1]));
+
+        // Shuffle the start times so that time order and index order disagree
+        reader.reset();
+        let mut spectra: std::collections::VecDeque<_> = reader.iter().collect();
+        let k = spectra.len();
+        spectra[0].description_mut().acquisition.first_scan_mut().unwrap().start_time = 1000.0;
+        spectra[k - 1].description_mut().acquisition.first_scan_mut().unwrap().start_time = -1.0;
+        let expected_first = spectra[0].id().to_string();
+        let expected_last = spectra[k - 1].id().to_string();
+
+        let mut source = MemorySpectrumSource::new(spectra);
+        let mut it = source.iter_rev_time();
+        assert_eq!(it.size_hint(), (k, Some(k)));
+        let ids: Vec<String> = it.by_ref().map(|s| s.id().to_string()).collect();
+        assert_eq!(ids.first().unwrap(), &expected_first);
+        assert_eq!(ids.last().unwrap(), &expected_last);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        Ok(())
+    }
 }
@@ -150,6 +150,20 @@ pub trait SpectrumSource<
         SpectrumIterator::new(self)
     }
 
+    /// Open a new iterator over this stream which yields spectra in descending order of
+    /// scan start time, from the latest to the earliest.
+    ///
+    /// Unlike reversing [`SpectrumSource::iter`], which follows index order, this follows
+    /// the spectra's actual start times, which may differ when the source isn't stored in
+    /// chronological order. The ordering is built by reading the metadata of every spectrum
+    /// once up front, so the source must be indexed.
+    fn iter_rev_time(&mut self) -> ReverseTimeSpectrumIterator<'_, C, D, S, Self>
+    where
+        Self: Sized,
+    {
+        ReverseTimeSpectrumIterator::new(self)
+    }
+
     /// Create a new [`SpectrumIterator`] over `self` and use that state to drive a [`SpectrumGroupingIterator`]
     fn groups(&mut self) -> SpectrumGroupingIterator<SpectrumIterator<'_, C, D, S, Self>, C, D, S>
     where
@@ -258,6 +272,78 @@ impl<
     }
 }
 
+/// An iterator over a [`SpectrumSource`] implementer that yields spectra in descending
+/// order of scan start time. Created by [`SpectrumSource::iter_rev_time`].
+pub struct ReverseTimeSpectrumIterator<
+    'lifespan,
+    C: CentroidLike + Default,
+    D: DeconvolutedCentroidLike + Default,
+    S: SpectrumLike<C, D>,
+    R: SpectrumSource<C, D, S>,
+> {
+    source: &'lifespan mut R,
+    order: Vec<usize>,
+    position: usize,
+    spectrum_type: PhantomData<S>,
+    centroid_type: PhantomData<C>,
+    deconvoluted_type: PhantomData<D>,
+}
+
+impl<
+        'lifespan,
+        C: CentroidLike + Default,
+        D: DeconvolutedCentroidLike + Default,
+        R: SpectrumSource<C, D, S>,
+        S: SpectrumLike<C, D>,
+    > ReverseTimeSpectrumIterator<'lifespan, C, D, S, R>
+{
+    pub fn new(source: &'lifespan mut R) -> Self {
+        let original_detail_level = *source.detail_level();
+        source.set_detail_level(DetailLevel::MetadataOnly);
+        let mut times: Vec<(usize, f64)> = (0..source.len())
+            .filter_map(|i| {
+                source
+                    .get_spectrum_by_index(i)
+                    .map(|scan| (i, scan.start_time()))
+            })
+            .collect();
+        source.set_detail_level(original_detail_level);
+
+        // Latest first, breaking ties by the later index
+        times.sort_by(|(i, a), (j, b)| b.total_cmp(a).then_with(|| j.cmp(i)));
+        Self {
+            source,
+            order: times.into_iter().map(|(i, _)| i).collect(),
+            position: 0,
+            spectrum_type: PhantomData,
+            centroid_type: PhantomData,
+            deconvoluted_type: PhantomData,
+        }
+    }
+}
+
+impl<
+        'lifespan,
+        C: CentroidLike + Default,
+        D: DeconvolutedCentroidLike + Default,
+        R: SpectrumSource<C, D, S>,
+        S: SpectrumLike<C, D>,
+    > Iterator for ReverseTimeSpectrumIterator<'lifespan, C, D, S, R>
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = *self.order.get(self.position)?;
+        self.position += 1;
+        self.source.get_spectrum_by_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.order.len() - self.position;
+        (n, Some(n))
+    }
+}
+
 impl<
         'lifespan,
         C: CentroidLike + Default,
@@ -550,6 +636,81 @@ impl<
 {
 }
 
+/// An object-safe view of a [`SpectrumSource`] with metadata, fixed to the default peak and
+/// spectrum types, [`CentroidPeak`], [`DeconvolutedPeak`], and [`MultiLayerSpectrum`].
+///
+/// This makes it possible to hold readers of different formats uniformly, e.g. in a
+/// `Vec<Box<dyn DynSpectrumSource>>`. Every [`SpectrumSource`] which also implements
+/// [`MSDataFileMetadata`] implements this trait automatically. [`MZReader`](crate::io::MZReader)
+/// covers the formats supported by this crate with an `enum`, while this trait permits
+/// third party readers to participate as well.
+///
+/// The methods mirror those of [`SpectrumSource`] with a `dyn_` prefix so that both traits
+/// can be in scope at once without making method calls on concrete readers ambiguous.
+pub trait DynSpectrumSource: Iterator<Item = MultiLayerSpectrum> + MSDataFileMetadata {
+    /// Rewind the current position of the source to the beginning
+    fn dyn_reset(&mut self);
+
+    /// Get the [`DetailLevel`] the reader currently uses
+    fn dyn_detail_level(&self) -> &DetailLevel;
+
+    /// Set the [`DetailLevel`] for the reader
+    fn dyn_set_detail_level(&mut self, detail_level: DetailLevel);
+
+    /// Retrieve a spectrum by it's native ID
+    fn dyn_get_spectrum_by_id(&mut self, id: &str) -> Option<MultiLayerSpectrum>;
+
+    /// Retrieve a spectrum by it's integer index
+    fn dyn_get_spectrum_by_index(&mut self, index: usize) -> Option<MultiLayerSpectrum>;
+
+    /// Retrieve a spectrum by its scan start time
+    fn dyn_get_spectrum_by_time(&mut self, time: f64) -> Option<MultiLayerSpectrum>;
+
+    /// Retrieve the number of spectra in source file
+    fn dyn_len(&self) -> usize;
+
+    fn dyn_is_empty(&self) -> bool {
+        self.dyn_len() == 0
+    }
+
+    /// Access the spectrum offset index to enumerate all spectra by ID
+    fn dyn_get_index(&self) -> &OffsetIndex;
+}
+
+impl<T: SpectrumSource + MSDataFileMetadata> DynSpectrumSource for T {
+    fn dyn_reset(&mut self) {
+        SpectrumSource::reset(self)
+    }
+
+    fn dyn_detail_level(&self) -> &DetailLevel {
+        SpectrumSource::detail_level(self)
+    }
+
+    fn dyn_set_detail_level(&mut self, detail_level: DetailLevel) {
+        SpectrumSource::set_detail_level(self, detail_level)
+    }
+
+    fn dyn_get_spectrum_by_id(&mut self, id: &str) -> Option<MultiLayerSpectrum> {
+        SpectrumSource::get_spectrum_by_id(self, id)
+    }
+
+    fn dyn_get_spectrum_by_index(&mut self, index: usize) -> Option<MultiLayerSpectrum> {
+        SpectrumSource::get_spectrum_by_index(self, index)
+    }
+
+    fn dyn_get_spectrum_by_time(&mut self, time: f64) -> Option<MultiLayerSpectrum> {
+        SpectrumSource::get_spectrum_by_time(self, time)
+    }
+
+    fn dyn_len(&self) -> usize {
+        SpectrumSource::len(self)
+    }
+
+    fn dyn_get_index(&self) -> &OffsetIndex {
+        SpectrumSource::get_index(self)
+    }
+}
+
 /// An alternative implementation of [`SpectrumSource`] for non-rewindable underlying iterators.
 ///
 /// When the source doesn't support [`io::Seek`](std::io::Seek), most reader types don't
@@ -799,13 +960,9 @@ impl<
     type Item = S;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.receiver.recv() {
-            Ok(s) => Some(s),
-            Err(e) => {
-                log::warn!("Failed to receive spectrum: {}", e);
-                None
-            }
-        }
+        // `recv` only fails once every sender has hung up and the channel is drained,
+        // which is the normal end of the stream
+        self.receiver.recv().ok()
     }
 }
 
@@ -4,7 +4,6 @@ use std::fs;
 use std::io;
 use std::path;
 use std::path::PathBuf;
-use std::io::prelude::*;
 
 use md5::Context as MD5Context;
 use md5::Digest;
@@ -106,38 +105,153 @@ where
     }
 }
 
-/// A writable stream that keeps a running MD5 checksum of all bytes
+/// A hash algorithm supported by [`HashingReader`] and [`HashingWriter`]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[default]
+    SHA1,
+    MD5,
+}
+
+/// The running state of a digest computed with a [`HashAlgorithm`]
+#[derive(Clone)]
+enum HashContext {
+    SHA1(sha1::Sha1),
+    MD5(MD5Context),
+}
+
+impl HashContext {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::SHA1 => Self::SHA1(sha1::Sha1::new()),
+            HashAlgorithm::MD5 => Self::MD5(MD5Context::new()),
+        }
+    }
+
+    fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            Self::SHA1(_) => HashAlgorithm::SHA1,
+            Self::MD5(_) => HashAlgorithm::MD5,
+        }
+    }
+
+    fn update(&mut self, buf: &[u8]) {
+        match self {
+            Self::SHA1(ctx) => ctx.update(buf),
+            Self::MD5(ctx) => ctx.consume(buf),
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        match self {
+            Self::SHA1(ctx) => ctx.clone().finalize().to_vec(),
+            Self::MD5(ctx) => {
+                let digest: Digest = ctx.clone().compute();
+                digest.0.to_vec()
+            }
+        }
+    }
+}
+
+/// A readable stream that keeps a running checksum of all bytes read through it
 #[derive(Clone)]
-pub(crate) struct MD5HashingStream<T: io::Write> {
-    pub stream: T,
-    pub context: MD5Context,
+pub struct HashingReader<R: io::Read> {
+    stream: R,
+    context: HashContext,
 }
 
-impl<T: io::Write> MD5HashingStream<T> {
-    pub fn new(file: T) -> MD5HashingStream<T> {
+impl<R: io::Read> HashingReader<R> {
+    /// Wrap `stream`, hashing the bytes read from it with `algorithm`
+    pub fn new(stream: R, algorithm: HashAlgorithm) -> Self {
         Self {
-            stream: file,
-            context: MD5Context::new(),
+            stream,
+            context: HashContext::new(algorithm),
         }
     }
 
-    pub fn compute(&self) -> Digest {
-        self.context.clone().compute()
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.context.algorithm()
+    }
+
+    /// The digest of all bytes read so far
+    pub fn digest(&self) -> Vec<u8> {
+        self.context.digest()
+    }
+
+    /// The digest of all bytes read so far as a lower-case hexadecimal string
+    pub fn hexdigest(&self) -> String {
+        base16ct::lower::encode_string(&self.digest())
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.stream
     }
 
-    pub fn get_mut(&mut self) -> &mut T {
+    pub fn get_mut(&mut self) -> &mut R {
         &mut self.stream
     }
 
-    pub fn into_inner(self) -> T {
+    pub fn into_inner(self) -> R {
         self.stream
     }
 }
 
-impl<T: io::Write> io::Write for MD5HashingStream<T> {
+impl<R: io::Read> io::Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.stream.read(buf)?;
+        self.context.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// A writable stream that keeps a running checksum of all bytes written through it
+#[derive(Clone)]
+pub struct HashingWriter<W: io::Write> {
+    stream: W,
+    context: HashContext,
+}
+
+impl<W: io::Write> HashingWriter<W> {
+    /// Wrap `stream`, hashing the bytes written to it with `algorithm`
+    pub fn new(stream: W, algorithm: HashAlgorithm) -> Self {
+        Self {
+            stream,
+            context: HashContext::new(algorithm),
+        }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.context.algorithm()
+    }
+
+    /// The digest of all bytes written so far
+    pub fn digest(&self) -> Vec<u8> {
+        self.context.digest()
+    }
+
+    /// The digest of all bytes written so far as a lower-case hexadecimal string
+    pub fn hexdigest(&self) -> String {
+        base16ct::lower::encode_string(&self.digest())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+impl<W: io::Write> io::Write for HashingWriter<W> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.context.consume(buf);
-        self.stream.write(buf)
+        let n = self.stream.write(buf)?;
+        self.context.update(&buf[..n]);
+        Ok(n)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -145,7 +259,7 @@ impl<T: io::Write> io::Write for MD5HashingStream<T> {
     }
 }
 
-impl<T: io::Seek + io::Write> io::Seek for MD5HashingStream<T> {
+impl<W: io::Seek + io::Write> io::Seek for HashingWriter<W> {
     fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
         self.stream.seek(pos)
     }
@@ -285,22 +399,15 @@ impl<R: io::Read> PreBufferedStream<R> {
 
 /// Compute a SHA-1 digest of a file path
 pub fn checksum_file(path: &PathBuf) -> io::Result<String> {
-    let mut checksum = sha1::Sha1::new();
-    let mut reader = io::BufReader::new(fs::File::open(path)?);
-    let mut buf = vec![0; 2usize.pow(20)];
-    while let Ok(i) = reader.read(&mut buf) {
-        if i == 0 {
-            break;
-        }
-        checksum.update(&buf[..i]);
-    }
-    let x = base16ct::lower::encode_string(&checksum.finalize());
-    Ok(x)
+    let mut reader = HashingReader::new(fs::File::open(path)?, HashAlgorithm::SHA1);
+    io::copy(&mut reader, &mut io::sink())?;
+    Ok(reader.hexdigest())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::io::prelude::*;
 
     #[test]
     fn test_from_buffer() {
@@ -316,6 +423,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_hashing_streams() -> io::Result<()> {
+        let content = b"foobar".repeat(1000);
+        for (algorithm, expected) in [
+            (HashAlgorithm::SHA1, "98fe86334ea7fa6a34a4844d9c837321931924be"),
+            (HashAlgorithm::MD5, "9df8608527e4c8acfc5cd4e4f3a85d04"),
+        ] {
+            let mut reader = HashingReader::new(io::Cursor::new(&content), algorithm);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            assert_eq!(buf, content);
+            assert_eq!(reader.algorithm(), algorithm);
+            assert_eq!(reader.hexdigest(), expected);
+
+            let mut writer = HashingWriter::new(Vec::new(), algorithm);
+            writer.write_all(&content[..10])?;
+            writer.write_all(&content[10..])?;
+            assert_eq!(writer.hexdigest(), expected);
+            assert_eq!(writer.digest(), reader.digest());
+            assert_eq!(writer.into_inner(), content);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_prebuffering() -> io::Result<()> {
         let mut fh = fs::File::open("./test/data/batching_test.mzML")?;
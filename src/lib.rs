@@ -15,6 +15,7 @@
 //!   3. mzMLb files using [`MzMLbWriter`] in [`mzdata::io::mzmlb`](crate::io::mzmlb), if the `mzmlb` feature is enabled
 //!
 //! This menagerie of different formats and gzip compression or not can be inferred from a path or [`io::Read`](std::io::Read) using [`io::infer_format`] and [`io::infer_from_stream`].
+//! Besides gzip, bzip2 and xz compressed files are recognized and transparently decompressed if the `bzip2` or `xz` features are enabled.
 //! Conventional dispatch is possible through [`MZReader`]. The [`mz_read`] macro provides a convenient means of working with
 //! a value with zero added overhead, but with a limited scope. The [`mz_write`] macro is the equivalent for opening a writer.
 //! There are additional tools for dealing with file format dispatch in [`MassSpectrometryReadWriteProcess`](crate::io::MassSpectrometryReadWriteProcess).
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::params::Unit;
+
 
 
 crate::cvmap! {
@@ -176,6 +178,16 @@ impl DissociationEnergyTerm {
         matches!(self, Self::CollisionEnergyRampEnd(_) | Self::PercentCollisionEnergyRampEnd(_))
     }
 
+    /// The unit this kind of energy is expressed in when the source does not specify one
+    pub const fn default_unit(&self) -> Unit {
+        match self {
+            Self::NormalizedCollisionEnergy(_)
+            | Self::PercentCollisionEnergyRampStart(_)
+            | Self::PercentCollisionEnergyRampEnd(_) => Unit::PercentElectronVolt,
+            _ => Unit::Electronvolt,
+        }
+    }
+
     pub const fn energy(&self) -> f32 {
         *match self {
             DissociationEnergyTerm::CollisionEnergy(x) => x,
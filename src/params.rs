@@ -90,6 +90,16 @@ pub trait ParamValue {
         Ok(v as f32)
     }
 
+    /// Get the value as an `f64`, if possible, also accepting a comma as the decimal
+    /// separator (e.g. `"1,5"` or `"1.234,5"`) as written by tools running under some
+    /// locales.
+    ///
+    /// This is opt-in because a lone comma is ambiguous: `"1,234"` is read as `1.234`.
+    fn to_f64_lenient(&self) -> Result<f64, ParamValueParseError> {
+        self.to_f64()
+            .or_else(|err| parse_f64_lenient(&self.to_str()).ok_or(err))
+    }
+
     /// Get the value as a `bool`, if possible
     fn to_bool(&self) -> Result<bool, ParamValueParseError>;
 
@@ -148,6 +158,47 @@ pub enum ParamValueParseError {
     FailedToExtractString,
     #[error("Failed to extract a buffer")]
     FailedToExtractBuffer,
+    #[error("Failed to extract {expected} from {value:?} of {param}")]
+    FailedToParseParam {
+        param: String,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+/// Parse a floating point number, falling back to treating a comma as the decimal separator
+/// and a period as a digit group separator when the plain parse fails.
+fn parse_f64_lenient(s: &str) -> Option<f64> {
+    let s = s.trim();
+    if let Ok(v) = s.parse() {
+        return Some(v);
+    }
+    let decimal = s.rfind(',')?;
+    if s[decimal + 1..].contains('.') {
+        return None;
+    }
+    let (integral, fractional) = s.split_at(decimal);
+    let integral = integral.replace('.', "");
+    if integral.contains(',') {
+        return None;
+    }
+    format!("{integral}.{}", &fractional[1..]).parse().ok()
+}
+
+/// Build a [`ParamValueParseError`] that names the offending parameter
+fn param_parse_error<P: ParamLike + ?Sized>(
+    param: &P,
+    expected: &'static str,
+) -> ParamValueParseError {
+    let name = match param.curie() {
+        Some(curie) => format!("{curie}|{}", param.name()),
+        None => param.name().to_string(),
+    };
+    ParamValueParseError::FailedToParseParam {
+        param: name,
+        value: param.value().to_string(),
+        expected,
+    }
 }
 
 impl FromStr for Value {
@@ -1432,10 +1483,17 @@ impl<'a> ParamValue for ParamCow<'a> {
 
     fn to_f64(&self) -> Result<f64, ParamValueParseError> {
         <ValueRef<'a> as ParamValue>::to_f64(&self.value)
+            .map_err(|_| param_parse_error(self, "a float"))
     }
 
     fn to_i64(&self) -> Result<i64, ParamValueParseError> {
         <ValueRef<'a> as ParamValue>::to_i64(&self.value)
+            .map_err(|_| param_parse_error(self, "an int"))
+    }
+
+    fn to_f64_lenient(&self) -> Result<f64, ParamValueParseError> {
+        <ValueRef<'a> as ParamValue>::to_f64_lenient(&self.value)
+            .map_err(|_| param_parse_error(self, "a float"))
     }
 
     fn to_str(&self) -> Cow<'_, str> {
@@ -1607,11 +1665,16 @@ impl ParamValue for Param {
     }
 
     fn to_f64(&self) -> Result<f64, ParamValueParseError> {
-        <Value as ParamValue>::to_f64(&self.value)
+        <Value as ParamValue>::to_f64(&self.value).map_err(|_| param_parse_error(self, "a float"))
     }
 
     fn to_i64(&self) -> Result<i64, ParamValueParseError> {
-        <Value as ParamValue>::to_i64(&self.value)
+        <Value as ParamValue>::to_i64(&self.value).map_err(|_| param_parse_error(self, "an int"))
+    }
+
+    fn to_f64_lenient(&self) -> Result<f64, ParamValueParseError> {
+        <Value as ParamValue>::to_f64_lenient(&self.value)
+            .map_err(|_| param_parse_error(self, "a float"))
     }
 
     fn to_str(&self) -> Cow<'_, str> {
@@ -2297,6 +2360,11 @@ pub enum Unit {
     // Wavelength
     Nanometer,
 
+    // Instrument conditions
+    Pascal,
+    DegreeCelsius,
+    MicrolitersPerMinute,
+
     // Time
     Minute,
     Second,
@@ -2342,6 +2410,11 @@ impl Unit {
 
             Self::Dimensionless => ("UO:0000186", "dimensionless unit"),
 
+            Self::Nanometer => ("UO:0000018", "nanometer"),
+            Self::Pascal => ("UO:0000110", "pascal"),
+            Self::DegreeCelsius => ("UO:0000027", "degree Celsius"),
+            Self::MicrolitersPerMinute => ("UO:0000271", "microliters per minute"),
+
             _ => ("", ""),
         }
     }
@@ -2368,6 +2441,11 @@ impl Unit {
             b"dimensionless unit" => Self::Dimensionless,
             b"volt-second per square centimeter" => Self::VoltSecondPerSquareCentimeter,
 
+            b"nanometer" => Self::Nanometer,
+            b"pascal" => Self::Pascal,
+            b"degree Celsius" => Self::DegreeCelsius,
+            b"microliters per minute" => Self::MicrolitersPerMinute,
+
             _ => Unit::Unknown,
         }
     }
@@ -2395,6 +2473,11 @@ impl Unit {
 
             b"MS:1002814" => Self::VoltSecondPerSquareCentimeter,
 
+            b"UO:0000018" => Self::Nanometer,
+            b"UO:0000110" => Self::Pascal,
+            b"UO:0000027" => Self::DegreeCelsius,
+            b"UO:0000271" => Self::MicrolitersPerMinute,
+
             _ => Unit::Unknown,
         }
     }
@@ -2456,6 +2539,23 @@ impl Unit {
                 controlled_vocabulary: ControlledVocabulary::MS,
                 accession: 1002814,
             } => Self::VoltSecondPerSquareCentimeter,
+
+            CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 18,
+            } => Self::Nanometer,
+            CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 110,
+            } => Self::Pascal,
+            CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 27,
+            } => Self::DegreeCelsius,
+            CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 271,
+            } => Self::MicrolitersPerMinute,
             _ => Unit::Unknown,
         }
     }
@@ -2519,6 +2619,23 @@ impl Unit {
                 controlled_vocabulary: ControlledVocabulary::MS,
                 accession: 1002814,
             }),
+
+            Self::Nanometer => Some(CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 18,
+            }),
+            Self::Pascal => Some(CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 110,
+            }),
+            Self::DegreeCelsius => Some(CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 27,
+            }),
+            Self::MicrolitersPerMinute => Some(CURIE {
+                controlled_vocabulary: ControlledVocabulary::UO,
+                accession: 271,
+            }),
             _ => None,
         }
     }
@@ -2544,6 +2661,48 @@ impl Display for Unit {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_parse_numeric_values() {
+        let val = Value::new("1.23E-4".to_string());
+        assert_eq!(val, Value::Float(1.23e-4));
+        assert_eq!(Value::String("-5e3".into()).to_f64().unwrap(), -5000.0);
+        assert_eq!(ValueRef::String("2.5e+2".into()).to_f64().unwrap(), 250.0);
+
+        let val = Value::String("1,5".into());
+        assert!(val.to_f64().is_err());
+        assert_eq!(val.to_f64_lenient().unwrap(), 1.5);
+        let val = ValueRef::String(" 1.234,5 ".into());
+        assert_eq!(val.to_f64_lenient().unwrap(), 1234.5);
+        let val = Value::String("1,5e-2".into());
+        assert_eq!(val.to_f64_lenient().unwrap(), 0.015);
+        assert!(Value::String("1,234,5".into()).to_f64_lenient().is_err());
+        assert!(Value::String("1,2.5".into()).to_f64_lenient().is_err());
+
+        let param = ControlledVocabulary::MS.param_val(1000016, "scan start time", "1,25");
+        assert_eq!(param.to_f64_lenient().unwrap(), 1.25);
+        let err = param.to_f64().unwrap_err();
+        assert_eq!(
+            err,
+            ParamValueParseError::FailedToParseParam {
+                param: "MS:1000016|scan start time".into(),
+                value: "1,25".into(),
+                expected: "a float",
+            }
+        );
+        let msg = err.to_string();
+        assert!(msg.contains("MS:1000016") && msg.contains("1,25"), "{msg}");
+
+        let param = ParamCow::new(
+            Cow::Borrowed("count"),
+            ValueRef::String("n/a".into()),
+            None,
+            None,
+            Unit::Unknown,
+        );
+        let msg = param.to_i64().unwrap_err().to_string();
+        assert!(msg.contains("count") && msg.contains("n/a"), "{msg}");
+    }
+
     #[test]
     fn test_build_param() {
         assert_eq!(
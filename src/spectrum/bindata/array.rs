@@ -201,6 +201,11 @@ impl<'transient, 'lifespan: 'transient> DataArray {
             BinaryCompressionType::Decoded => Cow::Borrowed(self.data.as_slice()),
             _ => self.decode().expect("Failed to decode binary data"),
         };
+        // An empty array is written as an empty string regardless of compression, matching
+        // how empty arrays are read back.
+        if bytestring.is_empty() {
+            return Bytes::new();
+        }
         match compression {
             BinaryCompressionType::Zlib => {
                 let compressed = Self::compress_zlib(&bytestring);
@@ -230,6 +235,19 @@ impl<'transient, 'lifespan: 'transient> DataArray {
         decompressor.finish().unwrap_or_else(|e| panic!("Decompression error: {}", e))
     }
 
+    /// A fallible version of [`DataArray::decompres_zlib`] which reports malformed
+    /// input as an error instead of panicking.
+    pub fn try_decompress_zlib(bytestring: &[u8]) -> Result<Bytes, ArrayRetrievalError> {
+        let result = Bytes::new();
+        let mut decompressor = ZlibDecoder::new(result);
+        decompressor
+            .write_all(bytestring)
+            .map_err(|e| ArrayRetrievalError::DecompressionError(e.to_string()))?;
+        decompressor
+            .finish()
+            .map_err(|e| ArrayRetrievalError::DecompressionError(e.to_string()))
+    }
+
     #[cfg(feature = "numpress")]
     pub fn compress_numpress_linear(data: &[f64]) -> Result<Bytes, ArrayRetrievalError> {
         let scaling = numpress::optimal_scaling(data);
@@ -272,6 +290,129 @@ impl<'transient, 'lifespan: 'transient> DataArray {
         }
     }
 
+    /// Decode and store the buffer like [`DataArray::decode_and_store`], but tolerate a
+    /// mislabeled compression method.
+    ///
+    /// If decoding with the declared compression fails, or does not produce `expected_len`
+    /// entries when an expected length is given, the other plausible methods (no compression,
+    /// zlib, and MS-Numpress linear prediction when the `numpress` feature is enabled) are tried
+    /// in turn. The first one that yields exactly `expected_len` entries is stored and a warning
+    /// about the mismatch is logged.
+    ///
+    /// The expected length only chooses between methods: if the declared compression decoded
+    /// without error but no other method matches the expected length, the declared result is
+    /// kept. If the declared compression failed and no other method succeeds, its error is
+    /// returned.
+    pub fn decode_and_store_lenient(
+        &mut self,
+        expected_len: Option<usize>,
+    ) -> Result<BinaryCompressionType, ArrayRetrievalError> {
+        if self.data.is_empty() || matches!(self.compression, BinaryCompressionType::Decoded) {
+            return self.decode_and_store();
+        }
+        let declared = self.compression;
+        let size = self.dtype.size_of();
+        let declared_result = match self.try_decode_as(declared, None) {
+            Ok(buffer) if expected_len.is_none_or(|n| buffer.len() / size == n) => {
+                self.item_count = Some(buffer.len() / size);
+                self.data = buffer;
+                self.compression = BinaryCompressionType::Decoded;
+                return Ok(self.compression);
+            }
+            result => result,
+        };
+
+        let candidates = [
+            BinaryCompressionType::NoCompression,
+            BinaryCompressionType::Zlib,
+            #[cfg(feature = "numpress")]
+            BinaryCompressionType::NumpressLinear,
+        ];
+        for candidate in candidates {
+            if candidate == declared {
+                continue;
+            }
+            if let Ok(buffer) = self.try_decode_as(candidate, expected_len) {
+                log::warn!(
+                    "{} array declared {:?} compression but was decoded as {:?}",
+                    self.name,
+                    declared,
+                    candidate
+                );
+                self.item_count = Some(buffer.len() / size);
+                self.data = buffer;
+                self.compression = BinaryCompressionType::Decoded;
+                return Ok(self.compression);
+            }
+        }
+        let buffer = declared_result?;
+        log::warn!(
+            "{} array decoded {} entries with its declared {:?} compression but {} were expected",
+            self.name,
+            buffer.len() / size,
+            declared,
+            expected_len.unwrap_or_default()
+        );
+        self.item_count = Some(buffer.len() / size);
+        self.data = buffer;
+        self.compression = BinaryCompressionType::Decoded;
+        Ok(self.compression)
+    }
+
+    /// Decode `self.data` as if it were encoded with `compression`, regardless of what
+    /// `self.compression` says, reporting malformed input as an error instead of panicking.
+    ///
+    /// If `expected_len` is provided, the result must contain exactly that many entries
+    /// of `self.dtype`.
+    fn try_decode_as(
+        &self,
+        compression: BinaryCompressionType,
+        expected_len: Option<usize>,
+    ) -> Result<Bytes, ArrayRetrievalError> {
+        let bytestring = base64_simd::STANDARD
+            .decode_type::<Bytes>(&self.data)
+            .map_err(|e| {
+                ArrayRetrievalError::DecompressionError(format!(
+                    "Failed to decode base64 array: {e}"
+                ))
+            })?;
+        let buffer = match compression {
+            BinaryCompressionType::NoCompression => bytestring,
+            BinaryCompressionType::Zlib => Self::try_decompress_zlib(&bytestring)?,
+            #[cfg(feature = "numpress")]
+            BinaryCompressionType::NumpressLinear => match self.dtype {
+                BinaryDataArrayType::Float64 => {
+                    vec_as_bytes(Self::decompres_numpress_linear(&bytestring)?)
+                }
+                _ => {
+                    return Err(ArrayRetrievalError::DecompressionError(
+                        compression.unsupported_msg(Some(
+                            format!("Not compatible with {:?}", self.dtype).as_str(),
+                        )),
+                    ))
+                }
+            },
+            mode => {
+                return Err(ArrayRetrievalError::DecompressionError(
+                    mode.unsupported_msg(None),
+                ))
+            }
+        };
+        let size = self.dtype.size_of();
+        if buffer.len() % size != 0 {
+            return Err(ArrayRetrievalError::DataTypeSizeMismatch);
+        }
+        if let Some(expected_len) = expected_len {
+            if buffer.len() / size != expected_len {
+                return Err(ArrayRetrievalError::DecompressionError(format!(
+                    "Expected {expected_len} entries but decoded {} with {compression:?}",
+                    buffer.len() / size
+                )));
+            }
+        }
+        Ok(buffer)
+    }
+
     /// Decompress and base64-decode encoded bytes, and return the data.
     ///
     /// If the data were already decoded, the existing bytes are returned. Otherwise one or
@@ -322,6 +463,13 @@ impl<'transient, 'lifespan: 'transient> DataArray {
         if start > end || (end - start) % self.dtype.size_of() != 0 {
             return Err(ArrayRetrievalError::DataTypeSizeMismatch);
         }
+        if self.data.is_empty() {
+            return if end == 0 {
+                Ok(Cow::Borrowed(&EMPTY_BUFFER))
+            } else {
+                Err(ArrayRetrievalError::DataTypeSizeMismatch)
+            };
+        }
         match self.compression {
             BinaryCompressionType::Decoded => Ok(Cow::Borrowed(&self.data.as_slice()[start..end])),
             BinaryCompressionType::NoCompression => {
@@ -621,6 +769,42 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_decode_lenient() -> io::Result<()> {
+        let reference = make_array_from_file()?;
+        let expected = reference.to_f64()?.len();
+
+        // zlib compressed bytes declared as uncompressed
+        let mut da = make_array_from_file()?;
+        da.compression = BinaryCompressionType::NoCompression;
+        da.decode_and_store_lenient(Some(expected))?;
+        assert_eq!(da.compression, BinaryCompressionType::Decoded);
+        assert_eq!(da.to_f64()?.len(), expected);
+
+        // uncompressed bytes declared as zlib compressed
+        let mut da = reference.clone();
+        da.decode_and_store()?;
+        da.store_compressed(BinaryCompressionType::NoCompression)?;
+        da.compression = BinaryCompressionType::Zlib;
+        da.decode_and_store_lenient(Some(expected))?;
+        assert_eq!(da.to_f64()?.len(), expected);
+        for (a, b) in reference.iter_f64()?.zip(da.iter_f64()?) {
+            assert_eq!(a, b);
+        }
+
+        // the declared length is off, but the declared compression is valid
+        let mut da = make_array_from_file()?;
+        da.decode_and_store_lenient(Some(expected + 1))?;
+        assert_eq!(da.compression, BinaryCompressionType::Decoded);
+        assert_eq!(da.to_f64()?.len(), expected);
+
+        // no method decodes at all
+        let mut da = make_array_from_file()?;
+        da.data.truncate(da.data.len() - 3);
+        assert!(da.decode_and_store_lenient(Some(expected)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_decode_empty() {
         let mut da = DataArray::wrap(&ArrayType::MZArray, BinaryDataArrayType::Float64, Vec::new());
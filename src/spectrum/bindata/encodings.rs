@@ -40,6 +40,7 @@ pub enum ArrayType {
     IntensityArray,
     ChargeArray,
     SignalToNoiseArray,
+    BaselineArray,
     TimeArray,
     WavelengthArray,
     IonMobilityArray,
@@ -104,6 +105,7 @@ impl ArrayType {
                 .into(),
             ArrayType::WavelengthArray => CV.const_param_ident_unit("wavelength array", 1000617, Unit::Nanometer).into(),
             ArrayType::SignalToNoiseArray => CV.const_param_ident("signal to noise array", 1000517).into(),
+            ArrayType::BaselineArray => CV.const_param_ident("baseline array", 1002530).into(),
             ArrayType::IonMobilityArray => CV.const_param_ident_unit("ion mobility array", 1002893, unit.unwrap_or_default()).into(),
             ArrayType::RawIonMobilityArray => CV
                 .const_param_ident_unit("raw ion mobility array", 1003007, unit.unwrap_or_default())
@@ -144,6 +146,7 @@ impl ArrayType {
             ArrayType::TimeArray => CV.const_param_ident_unit("time array", 1000595, Unit::Minute),
             ArrayType::WavelengthArray => CV.const_param_ident_unit("wavelength array", 1000617, Unit::Nanometer),
             ArrayType::SignalToNoiseArray => CV.const_param_ident("signal to noise array", 1000517),
+            ArrayType::BaselineArray => CV.const_param_ident("baseline array", 1002530),
             ArrayType::IonMobilityArray => CV.const_param_ident("ion mobility array", 1002893),
             ArrayType::RawIonMobilityArray => {
                 CV.const_param_ident("raw ion mobility array", 1003007)
@@ -445,6 +448,7 @@ mod test {
             ArrayType::IntensityArray,
             ArrayType::ChargeArray,
             ArrayType::SignalToNoiseArray,
+            ArrayType::BaselineArray,
             ArrayType::TimeArray,
             ArrayType::WavelengthArray,
             ArrayType::IonMobilityArray,
@@ -1,8 +1,15 @@
-use std::{marker::PhantomData, mem};
+use std::{io, marker::PhantomData, mem};
 
 use mzpeaks::{CentroidLike, CentroidPeak, DeconvolutedCentroidLike, DeconvolutedPeak};
 
-use super::super::{MultiLayerSpectrum, SpectrumLike};
+use crate::io::mzml::{MzMLParserState, MzMLReaderType, MzMLWriterType};
+use crate::io::SpectrumWriter;
+use crate::meta::MSDataFileMetadata;
+use crate::spectrum::bindata::{BuildArrayMapFrom, BuildFromArrayMap};
+
+use super::super::{
+    CentroidPeakAdapting, DeconvolutedPeakAdapting, MultiLayerSpectrum, SpectrumLike,
+};
 
 use super::util::GroupIterState;
 
@@ -147,6 +154,76 @@ where
     pub fn iter(&'a self) -> SpectrumGroupIter<'a, C, D, S> {
         SpectrumGroupIter::new(self)
     }
+
+    /// Create a copy of this group whose members carry only their [`SpectrumDescription`](crate::spectrum::SpectrumDescription),
+    /// dropping all peak and array data.
+    ///
+    /// This is a cheap way to retain the grouping structure and acquisition metadata of
+    /// a run without duplicating the signal.
+    pub fn metadata_only(&self) -> SpectrumGroup<C, D, MultiLayerSpectrum<C, D>> {
+        let strip = |s: &S| MultiLayerSpectrum {
+            description: s.description().clone(),
+            ..Default::default()
+        };
+        SpectrumGroup::new(
+            self.precursor.as_ref().map(strip),
+            self.products.iter().map(strip).collect(),
+        )
+    }
+}
+
+impl<C, D, S> SpectrumGroup<C, D, S>
+where
+    C: CentroidLike + Default + BuildArrayMapFrom + 'static,
+    D: DeconvolutedCentroidLike + Default + BuildArrayMapFrom + 'static,
+    S: SpectrumLike<C, D> + 'static,
+{
+    /// Write this group to a self-contained indexed mzML document in memory, taking
+    /// the file-level metadata from `metadata`.
+    ///
+    /// This is useful for passing individual acquisition events between processes as
+    /// a standard artifact. The buffer can be read back with [`SpectrumGroup::from_mzml_bytes`].
+    pub fn to_mzml_bytes(&self, metadata: &impl MSDataFileMetadata) -> io::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        {
+            let mut writer = MzMLWriterType::<_, C, D>::new(&mut buffer);
+            writer.copy_metadata_from(metadata);
+            writer.set_spectrum_count_hint(Some(self.total_spectra() as u64));
+            writer.write_group(self)?;
+            writer.close()?;
+        }
+        Ok(buffer)
+    }
+}
+
+impl<C, D> SpectrumGroup<C, D, MultiLayerSpectrum<C, D>>
+where
+    C: CentroidPeakAdapting + BuildFromArrayMap,
+    D: DeconvolutedPeakAdapting + BuildFromArrayMap,
+{
+    /// Read a group from an in-memory mzML document like those produced by
+    /// [`SpectrumGroup::to_mzml_bytes`].
+    ///
+    /// The first MS1 spectrum in the document becomes the precursor, and all other spectra
+    /// become products.
+    pub fn from_mzml_bytes(buffer: &[u8]) -> io::Result<Self> {
+        let mut reader = MzMLReaderType::<_, C, D>::new(io::Cursor::new(buffer));
+        let mut group = Self::default();
+        for spectrum in reader.by_ref() {
+            if group.precursor.is_none() && spectrum.ms_level() == 1 {
+                group.precursor = Some(spectrum);
+            } else {
+                group.products.push(spectrum);
+            }
+        }
+        if reader.state == MzMLParserState::ParserError {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Failed to parse spectrum group from mzML buffer",
+            ));
+        }
+        Ok(group)
+    }
 }
 
 pub struct SpectrumGroupIntoIter<
@@ -380,6 +457,7 @@ where
 
 #[cfg(test)]
 mod test {
+    use crate::io::{MZFileReader, SpectrumSource};
     use crate::Spectrum;
 
     use super::*;
@@ -430,4 +508,38 @@ mod test {
         assert_eq!(group.iter().count(), 3);
         assert_eq!(group.into_iter().count(), 3);
     }
+
+    #[test]
+    fn test_metadata_only() -> io::Result<()> {
+        let mut reader = crate::MzMLReader::open_path("./test/data/small.mzML")?;
+        let group = reader.groups().find(|g| !g.products.is_empty()).unwrap();
+        let stripped = group.metadata_only();
+
+        assert_eq!(group.total_spectra(), stripped.total_spectra());
+        for (a, b) in group.iter().zip(stripped.iter()) {
+            assert_eq!(a.description(), b.description());
+            assert!(a.raw_arrays().is_some());
+            assert!(b.raw_arrays().is_none());
+            assert_eq!(b.peaks().len(), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mzml_bytes_roundtrip() -> io::Result<()> {
+        let mut reader = crate::MzMLReader::open_path("./test/data/small.mzML")?;
+        let group = reader.groups().find(|g| !g.products.is_empty()).unwrap();
+        assert!(group.precursor.is_some());
+        assert!(!group.products.is_empty());
+
+        let buffer = group.to_mzml_bytes(&reader)?;
+        let dup: SpectrumGroup = SpectrumGroup::from_mzml_bytes(&buffer)?;
+        assert_eq!(group.total_spectra(), dup.total_spectra());
+        for (a, b) in group.iter().zip(dup.iter()) {
+            assert_eq!(a.id(), b.id());
+            assert_eq!(a.ms_level(), b.ms_level());
+            assert_eq!(a.peaks().len(), b.peaks().len());
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
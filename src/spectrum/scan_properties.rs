@@ -152,7 +152,11 @@ pub(crate) const ION_MOBILITY_SCAN_TERMS: [CURIE; 4] = [
     curie!(MS:1003371),
 ];
 
+/// Read a scalar ion mobility measure from a parameter list.
 pub trait IonMobilityMeasure: ParamDescribed {
+    /// Get the first ion mobility measure in the parameter list, checking the terms in the order
+    /// ion mobility drift time (MS:1002476), inverse reduced ion mobility drift time (MS:1002815),
+    /// FAIMS compensation voltage (MS:1001581), then SELEXION compensation voltage (MS:1003371).
     fn ion_mobility(&'_ self) -> Option<f64> {
         for u in ION_MOBILITY_SCAN_TERMS {
             if let Some(v) = self.get_param_by_curie(&u).map(|p| p.value()) {
@@ -377,11 +381,30 @@ impl IonMobilityMeasure for SelectedIon {}
 pub struct Activation {
     _methods: Vec<DissociationMethodTerm>,
     pub energy: f32,
+    /// The unit [`Activation::energy`] is expressed in, distinguishing absolute energies
+    /// ([`Unit::Electronvolt`]) from normalized ones ([`Unit::PercentElectronVolt`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub energy_unit: Unit,
     pub params: ParamList,
 }
 
 impl Activation {
 
+    /// Get the activation energy along with the unit it is expressed in.
+    ///
+    /// Normalized collision energies are reported in [`Unit::PercentElectronVolt`] and are
+    /// not interchangeable with absolute energies in [`Unit::Electronvolt`]. The unit is
+    /// [`Unit::Unknown`] when the source did not say which it was.
+    pub fn energy_with_unit(&self) -> (f32, Unit) {
+        (self.energy, self.energy_unit)
+    }
+
+    /// Set the activation energy and the unit it is expressed in
+    pub fn set_energy_with_unit(&mut self, energy: f32, unit: Unit) {
+        self.energy = energy;
+        self.energy_unit = unit;
+    }
+
     /// Get a reference to the first activation method, if it exists
     pub fn method(&self) -> Option<&DissociationMethodTerm> {
         self._methods.first()
@@ -719,6 +742,8 @@ impl SpectrumDescription {
 }
 
 impl_param_described!(Activation, SpectrumDescription);
+
+impl IonMobilityMeasure for SpectrumDescription {}
 impl_param_described_deferred!(SelectedIon, Acquisition, ScanEvent);
 
 /// Types of chromatograms enumerated in the PSI-MS controlled vocabulary
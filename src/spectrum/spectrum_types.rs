@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 use std::{borrow::Cow, ops::Index};
 
-use mzpeaks::Mass;
+use mzpeaks::{IntensityMeasurementMut, Mass, Tolerance};
 use thiserror::Error;
 
 use mzpeaks::{
@@ -130,11 +130,27 @@ pub trait SpectrumLike<
 
     /// Access the point measure of ion mobility associated with the scan if present. This is distinct from
     /// having a frame-level scan across the ion mobility dimension.
+    ///
+    /// Readers record this measure as a parameter, e.g. the drift time in mzML or the Thermo
+    /// FAIMS compensation voltage, so this is the same regardless of the source format.
+    ///
+    /// # Precedence
+    /// The first scan event in [`SpectrumLike::acquisition`] carrying a measure is used, falling
+    /// back to the spectrum-level parameters of [`SpectrumLike::description`], and then to the
+    /// first selected ion of [`SpectrumLike::precursor`]. The last case covers PASEF spectra
+    /// from TDF files, whose 1/K0 is only recorded on the precursor ion. Within a parameter
+    /// list, the order of precedence is given by [`IonMobilityMeasure::ion_mobility`].
     fn ion_mobility(&self) -> Option<f64> {
         self.acquisition()
             .iter()
             .flat_map(|s| s.ion_mobility())
             .next()
+            .or_else(|| self.description().ion_mobility())
+            .or_else(|| {
+                self.precursor()
+                    .and_then(|p| p.ions.first())
+                    .and_then(|ion| ion.ion_mobility())
+            })
     }
 
     /// Check if this spectrum has a point measure of ion mobility. This is distinct from
@@ -566,6 +582,8 @@ impl RawSpectrum {
 
     /// Access the point measure of ion mobility associated with the scan if present. This is distinct from
     /// having a frame-level scan across the ion mobility dimension.
+    ///
+    /// See [`SpectrumLike::ion_mobility`] for the order of precedence.
     pub fn ion_mobility(&self) -> Option<f64> {
         <RawSpectrum as SpectrumLike<CentroidPeak, DeconvolutedPeak>>::ion_mobility(self)
     }
@@ -718,6 +736,44 @@ impl<C: CentroidLike + Default> CentroidSpectrumType<C> {
     }
 }
 
+impl<C: CentroidLike + Default + CoordinateLikeMut<MZ> + IntensityMeasurementMut>
+    CentroidSpectrumType<C>
+{
+    /// Merge adjacent peaks whose m/z values fall within `tolerance` of one another
+    /// into a single peak, in place.
+    ///
+    /// Each merged peak takes the intensity-weighted mean m/z and the summed intensity
+    /// of its members. Peaks are compared against the running merged peak, so a chain of
+    /// split peaks will collapse together. The peak list remains sorted and the summary
+    /// statistics are updated afterwards.
+    ///
+    /// Returns the number of peaks removed by merging.
+    pub fn merge_close_peaks(&mut self, tolerance: Tolerance) -> usize {
+        let n_before = self.peaks.len();
+        let peaks = std::mem::take(&mut self.peaks);
+        let mut merged: Vec<C> = Vec::with_capacity(n_before);
+        for peak in peaks {
+            match merged.last_mut() {
+                Some(last) if tolerance.test(peak.mz(), last.mz()) => {
+                    let total = last.intensity() + peak.intensity();
+                    if total > 0.0 {
+                        *last.coordinate_mut() = (last.mz() * last.intensity() as f64
+                            + peak.mz() * peak.intensity() as f64)
+                            / total as f64;
+                    } else {
+                        *last.coordinate_mut() = (last.mz() + peak.mz()) / 2.0;
+                    }
+                    *last.intensity_mut() = total;
+                }
+                _ => merged.push(peak),
+            }
+        }
+        self.peaks = MZPeakSetType::new(merged);
+        self.update_summaries();
+        n_before - self.peaks.len()
+    }
+}
+
 pub type CentroidSpectrum = CentroidSpectrumType<CentroidPeak>;
 
 impl<C: CentroidPeakAdapting> Index<usize> for CentroidSpectrumType<C> {
@@ -1459,6 +1515,71 @@ mod test {
     use crate::io::DetailLevel;
     use crate::prelude::*;
 
+    #[test]
+    fn test_merge_close_peaks() {
+        let peaks = vec![
+            CentroidPeak::new(100.0, 100.0, 0),
+            CentroidPeak::new(100.0005, 300.0, 1),
+            CentroidPeak::new(150.0, 50.0, 2),
+            CentroidPeak::new(200.0, 20.0, 3),
+            CentroidPeak::new(200.001, 20.0, 4),
+            CentroidPeak::new(200.002, 20.0, 5),
+        ];
+        let mut spec =
+            CentroidSpectrum::new(SpectrumDescription::default(), MZPeakSetType::new(peaks));
+        let removed = spec.merge_close_peaks(Tolerance::PPM(10.0));
+        assert_eq!(removed, 3);
+        assert_eq!(spec.peaks.len(), 3);
+
+        let first = &spec.peaks[0];
+        assert!((first.mz - 100.000375).abs() < 1e-9, "{}", first.mz);
+        assert_eq!(first.intensity, 400.0);
+        assert_eq!(spec.peaks[1].mz, 150.0);
+        assert!((spec.peaks[2].mz - 200.001).abs() < 1e-9);
+        assert_eq!(spec.peaks[2].intensity, 60.0);
+        assert!(spec.peaks.iter().enumerate().all(|(i, p)| p.index as usize == i));
+
+        let tic = spec.get_param_by_curie(&crate::curie!(MS:1000285)).unwrap();
+        assert_eq!(tic.to_f64().unwrap(), 510.0);
+    }
+
+    #[test]
+    fn test_ion_mobility_precedence() {
+        use crate::params::ControlledVocabulary;
+        let ms = ControlledVocabulary::MS;
+
+        let mut spec = RawSpectrum::default();
+        spec.description.acquisition.scans.push(crate::spectrum::ScanEvent::default());
+        assert!(spec.ion_mobility().is_none());
+
+        // The precursor ion's measure is used when nothing else carries one, as the TDF
+        // reader records it for PASEF spectra
+        let mut ion = crate::spectrum::SelectedIon::default();
+        ion.add_param(ms.param_val(1002815, "inverse reduced ion mobility", 1.05));
+        let mut precursor = crate::spectrum::Precursor::default();
+        precursor.add_ion(ion);
+        spec.description.precursor = Some(precursor);
+        assert_eq!(spec.ion_mobility(), Some(1.05));
+
+        // A spectrum-level measure is used when no scan carries one
+        spec.description
+            .add_param(ms.param_val(1001581, "FAIMS compensation voltage", -45.0));
+        assert_eq!(spec.ion_mobility(), Some(-45.0));
+
+        // A scan-level measure takes precedence over the spectrum-level one
+        let scan = spec.description.acquisition.first_scan_mut().unwrap();
+        scan.add_param(ms.param_val(1002815, "inverse reduced ion mobility drift time", 0.95));
+        assert_eq!(spec.ion_mobility(), Some(0.95));
+
+        // Within a scan, drift time takes precedence over FAIMS CV regardless of position
+        let scan = spec.description.acquisition.first_scan_mut().unwrap();
+        scan.params_mut()
+            .insert(0, ms.param_val(1001581, "FAIMS compensation voltage", -30.0));
+        scan.add_param(ms.param_val(1002476, "ion mobility drift time", 12.5));
+        assert_eq!(spec.ion_mobility(), Some(12.5));
+        assert!(spec.has_ion_mobility());
+    }
+
     #[test_log::test]
     fn test_peakdata_lazy() -> io::Result<()> {
         let mut reader = MzMLReader::open_path("./test/data/small.mzML")?;